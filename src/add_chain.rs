@@ -0,0 +1,116 @@
+//! Asking wallets to add a chain.
+//!
+//! [EIP-3085] defines `wallet_addEthereumChain`, which dapps use to suggest a
+//! chain to a wallet. [`AddEthereumChainParameter::new`] builds its parameter
+//! from the endpoints a list has for a chain, and optionally the chain's
+//! [`ChainInfo`].
+//!
+//! ## Example
+//!
+//! ```
+//! use eip5139::add_chain::AddEthereumChainParameter;
+//! use eip5139::chains::{Registry, Snapshot};
+//! # let list = eip5139::RpcProviders::default();
+//!
+//! let info = Snapshot::bundled().chain(1);
+//!
+//! if let Some(parameter) = AddEthereumChainParameter::new(&list, 1, info) {
+//!     let request = serde_json::json!({
+//!         "method": "wallet_addEthereumChain",
+//!         "params": [parameter],
+//!     });
+//! }
+//! ```
+//!
+//! [EIP-3085]: https://eips.ethereum.org/EIPS/eip-3085
+
+use crate::chains::{ChainInfo, NativeCurrency};
+use crate::endpoint::Transport;
+use crate::{Endpoint, RpcProviders};
+
+use serde::Serialize;
+
+/// The parameter of a `wallet_addEthereumChain` request.
+///
+/// Serializes to the JSON object defined by EIP-3085, omitting empty fields.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct AddEthereumChainParameter {
+    /// The chain identifier, as a `0x` prefixed hex string.
+    pub chain_id: String,
+
+    /// Human-readable name of the chain.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_name: Option<String>,
+
+    /// The currency used to pay for gas.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub native_currency: Option<NativeCurrency>,
+
+    /// HTTP(S) endpoints for the chain, ordered by provider priority.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rpc_urls: Vec<String>,
+
+    /// Block explorers for the chain.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub block_explorer_urls: Vec<String>,
+
+    /// Logos of the providers serving the chain, ordered by provider
+    /// priority.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub icon_urls: Vec<String>,
+}
+
+fn push_unique(urls: &mut Vec<String>, url: &str) {
+    if !urls.iter().any(|u| u == url) {
+        urls.push(url.to_owned());
+    }
+}
+
+impl AddEthereumChainParameter {
+    /// Describe the chain identified by `chain_id`, using the endpoints from
+    /// `list` and the name, currency and explorers from `info`.
+    ///
+    /// Only HTTP(S) endpoints are included, since wallets don't accept other
+    /// transports. Returns `None` if `list` has no such endpoints for the
+    /// chain.
+    pub fn new(list: &RpcProviders, chain_id: u64, info: Option<&ChainInfo>) -> Option<Self> {
+        let mut rpc_urls = Vec::new();
+        let mut icon_urls = Vec::new();
+
+        for entry in list.endpoints_for(chain_id) {
+            let http = Endpoint::parse(entry.endpoint)
+                .map(|e| e.transport() == Some(Transport::Http))
+                .unwrap_or(false);
+
+            if !http {
+                continue;
+            }
+
+            push_unique(&mut rpc_urls, entry.endpoint);
+
+            if let Some(ref logo) = entry.provider.logo {
+                push_unique(&mut icon_urls, logo);
+            }
+        }
+
+        if rpc_urls.is_empty() {
+            return None;
+        }
+
+        let mut block_explorer_urls = Vec::new();
+        for explorer in info.map(|i| i.explorers.as_slice()).unwrap_or_default() {
+            push_unique(&mut block_explorer_urls, &explorer.url);
+        }
+
+        Some(Self {
+            chain_id: format!("{:#x}", chain_id),
+            chain_name: info.map(|i| i.name.clone()),
+            native_currency: info.map(|i| i.native_currency.clone()),
+            rpc_urls,
+            block_explorer_urls,
+            icon_urls,
+        })
+    }
+}
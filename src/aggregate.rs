@@ -0,0 +1,207 @@
+//! Merging several independent lists into one set of providers.
+//!
+//! ## Example
+//!
+//! ```
+//! use eip5139::aggregate::{Aggregator, ConflictPolicy};
+//! # let community = eip5139::RpcProviders::default();
+//! # let vendor = eip5139::RpcProviders::default();
+//!
+//! let merged = Aggregator::new(ConflictPolicy::UnionEndpoints)
+//!     .list(&community)
+//!     .list(&vendor)
+//!     .aggregate();
+//!
+//! for provider in merged.providers() {
+//!     println!("{} from {:?}", provider.provider.key, provider.origins);
+//! }
+//! ```
+
+use crate::query::by_priority;
+use crate::{Provider, RpcProviders};
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// How to combine providers with the same key from different lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConflictPolicy {
+    /// Keep the provider from the list added first.
+    PreferFirst,
+
+    /// Keep the provider with the highest priority (ie. the lowest
+    /// `priority` value), falling back to the list added first.
+    PreferHighestPriority,
+
+    /// Keep the metadata of the provider from the list added first, and
+    /// combine the chains and endpoints of every provider. The highest
+    /// priority is kept.
+    UnionEndpoints,
+}
+
+/// Identifies a list a provider came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Origin {
+    /// Position of the list, in the order lists were added.
+    pub index: usize,
+
+    /// Name of the list.
+    pub name: String,
+}
+
+/// A provider in an [`Aggregate`], and the lists it was found in.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct AggregatedProvider {
+    /// The merged provider.
+    pub provider: Provider,
+
+    /// Every list containing a provider with this key, in the order the
+    /// lists were added. The first origin is the one the provider's metadata
+    /// was taken from.
+    pub origins: Vec<Origin>,
+}
+
+/// Collects lists to merge.
+#[derive(Debug)]
+pub struct Aggregator<'a> {
+    policy: ConflictPolicy,
+    lists: Vec<&'a RpcProviders>,
+}
+
+impl<'a> Aggregator<'a> {
+    /// Create an aggregator resolving key collisions using `policy`.
+    pub fn new(policy: ConflictPolicy) -> Self {
+        Self {
+            policy,
+            lists: Vec::new(),
+        }
+    }
+
+    /// Add a list to merge.
+    pub fn list(mut self, list: &'a RpcProviders) -> Self {
+        self.lists.push(list);
+        self
+    }
+
+    /// Merge the lists.
+    pub fn aggregate(self) -> Aggregate {
+        let mut merged: Vec<AggregatedProvider> = Vec::new();
+        let mut by_key = HashMap::<String, usize>::new();
+
+        for (index, list) in self.lists.iter().enumerate() {
+            let origin = Origin {
+                index,
+                name: list.name.clone(),
+            };
+
+            for provider in list.providers() {
+                let idx = match by_key.get(&provider.key) {
+                    Some(idx) => *idx,
+                    None => {
+                        by_key.insert(provider.key.clone(), merged.len());
+                        merged.push(AggregatedProvider {
+                            provider: provider.clone(),
+                            origins: vec![origin.clone()],
+                        });
+                        continue;
+                    }
+                };
+
+                let existing = &mut merged[idx];
+
+                match self.policy {
+                    ConflictPolicy::PreferFirst => existing.origins.push(origin.clone()),
+                    ConflictPolicy::PreferHighestPriority => {
+                        if by_priority(provider, &existing.provider) == Ordering::Less {
+                            existing.provider = provider.clone();
+                            existing.origins.insert(0, origin.clone());
+                        } else {
+                            existing.origins.push(origin.clone());
+                        }
+                    }
+                    ConflictPolicy::UnionEndpoints => {
+                        union(&mut existing.provider, provider);
+                        existing.origins.push(origin.clone());
+                    }
+                }
+            }
+        }
+
+        for aggregated in &mut merged {
+            for chain in &mut aggregated.provider.chains {
+                let mut seen = Vec::with_capacity(chain.endpoints.len());
+                chain.endpoints.retain(|e| {
+                    let duplicate = seen.contains(e);
+                    if !duplicate {
+                        seen.push(e.clone());
+                    }
+                    !duplicate
+                });
+            }
+        }
+
+        merged.sort_by(|a, b| {
+            by_priority(&a.provider, &b.provider).then_with(|| a.provider.key.cmp(&b.provider.key))
+        });
+
+        Aggregate {
+            primary: self.lists.first().map(|l| (*l).clone_metadata()),
+            providers: merged,
+        }
+    }
+}
+
+/// Add the chains and endpoints of `other` to `into`.
+fn union(into: &mut Provider, other: &Provider) {
+    if by_priority(other, into) == Ordering::Less {
+        into.priority = other.priority;
+    }
+
+    for other_chain in &other.chains {
+        match into
+            .chains
+            .iter_mut()
+            .find(|c| c.chain_id == other_chain.chain_id)
+        {
+            Some(chain) => chain
+                .endpoints
+                .extend(other_chain.endpoints.iter().cloned()),
+            None => into.chains.push(other_chain.clone()),
+        }
+    }
+}
+
+/// The result of merging several lists.
+#[derive(Debug)]
+pub struct Aggregate {
+    primary: Option<RpcProviders>,
+    providers: Vec<AggregatedProvider>,
+}
+
+impl Aggregate {
+    /// Get the merged providers, ordered by priority.
+    pub fn providers(&self) -> &[AggregatedProvider] {
+        &self.providers
+    }
+
+    /// Get the lists containing the provider identified by `key`.
+    pub fn origins(&self, key: &str) -> Option<&[Origin]> {
+        self.providers
+            .iter()
+            .find(|p| p.provider.key == key)
+            .map(|p| p.origins.as_slice())
+    }
+
+    /// Convert the merged providers into a single list.
+    ///
+    /// The name, logo, version and timestamp are taken from the list added
+    /// first.
+    pub fn into_list(self) -> RpcProviders {
+        let mut list = self.primary.unwrap_or_default();
+        list.set_providers(self.providers.into_iter().map(|p| p.provider).collect());
+        list
+    }
+}
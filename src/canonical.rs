@@ -0,0 +1,186 @@
+//! Canonical JSON serialization and content hashing.
+//!
+//! The canonical form follows the JSON Canonicalization Scheme ([RFC 8785]):
+//! object members are sorted by the UTF-16 code units of their names,
+//! numbers are written the way ECMAScript writes them (so `1.0` and `1e0`
+//! both become `1`), and no insignificant whitespace is written.
+//!
+//! As in JCS, every number is treated as an IEEE-754 double, including
+//! integers: those beyond 2<sup>53</sup> are rounded to the nearest double,
+//! and written as ECMAScript would write that double. Documents
+//! that differ only in formatting or member order have the same canonical
+//! form, and so the same [`ContentHash`].
+//!
+//! This is the form list publishers sign (see
+//! [`signature`](crate::signature).)
+//!
+//! ## Example
+//!
+//! ```
+//! use eip5139::canonical::{self, Algorithm};
+//! use serde_json::json;
+//!
+//! let a = json!({ "b": 1.0, "a": [true, null] });
+//! let b = json!({ "a": [true, null], "b": 1 });
+//!
+//! assert_eq!(canonical::to_string(&a), r#"{"a":[true,null],"b":1}"#);
+//! assert_eq!(
+//!     canonical::hash(&a, Algorithm::Keccak256),
+//!     canonical::hash(&b, Algorithm::Keccak256),
+//! );
+//! ```
+//!
+//! [RFC 8785]: https://www.rfc-editor.org/rfc/rfc8785
+
+use serde::{Serialize, Serializer};
+
+use serde_json::{Number, Value};
+
+use sha2::Sha256;
+
+use sha3::{Digest, Keccak256};
+
+use std::fmt;
+
+/// Serialize `value` canonically.
+pub fn to_string(value: &Value) -> String {
+    let mut output = String::new();
+    write(&mut output, value);
+    output
+}
+
+fn write(output: &mut String, value: &Value) {
+    match value {
+        Value::Array(items) => {
+            output.push('[');
+            for (idx, item) in items.iter().enumerate() {
+                if idx != 0 {
+                    output.push(',');
+                }
+                write(output, item);
+            }
+            output.push(']');
+        }
+        Value::Object(members) => {
+            let mut members: Vec<_> = members.iter().collect();
+            members.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+
+            output.push('{');
+            for (idx, (name, member)) in members.into_iter().enumerate() {
+                if idx != 0 {
+                    output.push(',');
+                }
+                output.push_str(&serde_json::to_string(name).unwrap());
+                output.push(':');
+                write(output, member);
+            }
+            output.push('}');
+        }
+        Value::Number(number) => number_to_string(output, number),
+        scalar => output.push_str(&serde_json::to_string(scalar).unwrap()),
+    }
+}
+
+/// Write `number` as ECMAScript's `Number.prototype.toString` would.
+fn number_to_string(output: &mut String, number: &Number) {
+    let value = number.as_f64().unwrap();
+    if value == 0.0 {
+        output.push('0');
+        return;
+    }
+
+    if value < 0.0 {
+        output.push('-');
+    }
+
+    // Rust writes the shortest digits that round trip, like ECMAScript.
+    let scientific = format!("{:e}", value.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap();
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let exponent: i32 = exponent.parse().unwrap();
+
+    // The value is 0.DIGITS × 10^point.
+    let len = digits.len() as i32;
+    let point = exponent + 1;
+
+    if len <= point && point <= 21 {
+        output.push_str(&digits);
+        output.extend(std::iter::repeat('0').take((point - len) as usize));
+    } else if 0 < point && point <= 21 {
+        output.push_str(&digits[..point as usize]);
+        output.push('.');
+        output.push_str(&digits[point as usize..]);
+    } else if -6 < point && point <= 0 {
+        output.push_str("0.");
+        output.extend(std::iter::repeat('0').take(-point as usize));
+        output.push_str(&digits);
+    } else {
+        output.push_str(&digits[..1]);
+        if len > 1 {
+            output.push('.');
+            output.push_str(&digits[1..]);
+        }
+        let sign = if point > 0 { '+' } else { '-' };
+        output.push('e');
+        output.push(sign);
+        output.push_str(&(point - 1).abs().to_string());
+    }
+}
+
+/// A hash function for [`ContentHash`]es.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Algorithm {
+    /// Keccak-256, as used by Ethereum.
+    Keccak256,
+
+    /// SHA-256.
+    Sha256,
+}
+
+/// The hash of a document's canonical form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContentHash {
+    algorithm: Algorithm,
+    bytes: [u8; 32],
+}
+
+impl ContentHash {
+    /// The hash function used.
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// The raw hash.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.bytes
+    }
+}
+
+/// Written as `0x` followed by 64 hex digits.
+impl fmt::Display for ContentHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(self.bytes))
+    }
+}
+
+impl Serialize for ContentHash {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// Hash the canonical form of `value` with `algorithm`.
+pub fn hash(value: &Value, algorithm: Algorithm) -> ContentHash {
+    let canonical = to_string(value);
+
+    let bytes = match algorithm {
+        Algorithm::Keccak256 => Keccak256::digest(canonical.as_bytes()).into(),
+        Algorithm::Sha256 => Sha256::digest(canonical.as_bytes()).into(),
+    };
+
+    ContentHash { algorithm, bytes }
+}
@@ -0,0 +1,264 @@
+//! Names, currencies and block explorers for chains.
+//!
+//! Lists only identify chains by number. A [`Registry`] supplies the rest,
+//! in the format used by [chainlist] (`chains.json`.) A small [`Snapshot`]
+//! of well-known chains is bundled, and any other source of metadata can
+//! implement [`Registry`].
+//!
+//! ## Example
+//!
+//! ```
+//! use eip5139::chains::{self, Snapshot};
+//! # let mut list = eip5139::RpcProviders::default();
+//!
+//! let registry = Snapshot::bundled();
+//!
+//! for chain in chains::join(&list, registry) {
+//!     match chain.info {
+//!         Some(info) => println!("{} ({})", info.name, info.native_currency.symbol),
+//!         None => println!("unknown chain {}", chain.chain_id),
+//!     }
+//! }
+//!
+//! // Hide testnets, and chains the registry doesn't know about.
+//! chains::mainnets_only(&mut list, registry);
+//! ```
+//!
+//! [chainlist]: https://github.com/ethereum-lists/chains
+
+use crate::errors::JsonError;
+use crate::RpcProviders;
+
+use lazy_static::lazy_static;
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::{BTreeMap, HashMap};
+
+/// The currency used to pay for gas on a chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct NativeCurrency {
+    /// Name of the currency (eg. `Ether`.)
+    pub name: String,
+
+    /// Ticker symbol of the currency (eg. `ETH`.)
+    pub symbol: String,
+
+    /// Number of decimal places in one unit of the currency.
+    pub decimals: u8,
+}
+
+/// A block explorer for a chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Explorer {
+    /// Name of the explorer.
+    pub name: String,
+
+    /// Address of the explorer's home page.
+    pub url: String,
+
+    /// The standard the explorer's URLs follow, if any (eg. `EIP3091`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub standard: Option<String>,
+}
+
+/// Metadata describing a chain, as found in chainlist's `chains.json`.
+///
+/// Fields of the chainlist format not listed here are ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ChainInfo {
+    /// The identifier of the chain.
+    #[serde(rename = "chainId")]
+    pub chain_id: u64,
+
+    /// Human-readable name of the chain.
+    pub name: String,
+
+    /// Abbreviated name of the chain (eg. `eth`.)
+    #[serde(rename = "shortName", default)]
+    pub short_name: String,
+
+    /// The currency used to pay for gas.
+    #[serde(rename = "nativeCurrency")]
+    pub native_currency: NativeCurrency,
+
+    /// Block explorers for the chain.
+    #[serde(default)]
+    pub explorers: Vec<Explorer>,
+
+    /// Address of a page describing the chain.
+    #[serde(rename = "infoURL", default, skip_serializing_if = "Option::is_none")]
+    pub info_url: Option<String>,
+
+    /// Addresses of faucets handing out the chain's currency.
+    #[serde(default)]
+    pub faucets: Vec<String>,
+
+    /// The [SLIP-44] coin type of the chain's currency. Chainlist uses `1`
+    /// (shared by every testnet) to mark testnets.
+    ///
+    /// [SLIP-44]: https://github.com/satoshilabs/slips/blob/master/slip-0044.md
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slip44: Option<u32>,
+
+    /// Whether the chain is a testnet, overriding `slip44`. Not part of
+    /// chainlist's format, but useful for registries that know better.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub testnet: Option<bool>,
+}
+
+impl ChainInfo {
+    /// Whether the chain is a testnet.
+    ///
+    /// Uses [`testnet`](Self::testnet) if set, and otherwise chainlist's
+    /// convention of giving testnets a `slip44` of `1`.
+    pub fn is_testnet(&self) -> bool {
+        self.testnet.unwrap_or(self.slip44 == Some(1))
+    }
+}
+
+/// A source of [`ChainInfo`].
+pub trait Registry {
+    /// Metadata for the chain identified by `chain_id`, if known.
+    fn chain(&self, chain_id: u64) -> Option<&ChainInfo>;
+}
+
+impl<R> Registry for &R
+where
+    R: Registry + ?Sized,
+{
+    fn chain(&self, chain_id: u64) -> Option<&ChainInfo> {
+        (**self).chain(chain_id)
+    }
+}
+
+impl Registry for HashMap<u64, ChainInfo> {
+    fn chain(&self, chain_id: u64) -> Option<&ChainInfo> {
+        self.get(&chain_id)
+    }
+}
+
+impl Registry for BTreeMap<u64, ChainInfo> {
+    fn chain(&self, chain_id: u64) -> Option<&ChainInfo> {
+        self.get(&chain_id)
+    }
+}
+
+lazy_static! {
+    static ref BUNDLED: Snapshot = Snapshot::from_json(include_str!("chains.json")).unwrap();
+}
+
+/// A fixed set of [`ChainInfo`], such as a copy of chainlist's `chains.json`.
+#[derive(Debug, Clone, Default)]
+pub struct Snapshot {
+    chains: BTreeMap<u64, ChainInfo>,
+}
+
+impl Snapshot {
+    /// The snapshot bundled with this crate, covering well-known mainnets
+    /// and testnets.
+    pub fn bundled() -> &'static Self {
+        &BUNDLED
+    }
+
+    /// Parse a snapshot from a JSON array of chains in chainlist's format.
+    ///
+    /// If a chain appears more than once, the last entry is kept.
+    pub fn from_json(json: &str) -> Result<Self, JsonError> {
+        let chains: Vec<ChainInfo> = serde_json::from_str(json).map_err(JsonError)?;
+        Ok(chains.into_iter().collect())
+    }
+
+    /// Every chain in this snapshot, in ascending order of identifier.
+    pub fn iter(&self) -> impl Iterator<Item = &ChainInfo> + '_ {
+        self.chains.values()
+    }
+
+    /// Add `info` to this snapshot, replacing any entry for the same chain.
+    pub fn insert(&mut self, info: ChainInfo) -> Option<ChainInfo> {
+        self.chains.insert(info.chain_id, info)
+    }
+}
+
+impl FromIterator<ChainInfo> for Snapshot {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = ChainInfo>,
+    {
+        let mut snapshot = Self::default();
+        for info in iter {
+            snapshot.insert(info);
+        }
+        snapshot
+    }
+}
+
+impl Registry for Snapshot {
+    fn chain(&self, chain_id: u64) -> Option<&ChainInfo> {
+        self.chains.get(&chain_id)
+    }
+}
+
+/// A chain served by a list, with its metadata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ListedChain<'a> {
+    /// The identifier of the chain.
+    pub chain_id: u64,
+
+    /// Metadata for the chain, if the registry knows it.
+    pub info: Option<&'a ChainInfo>,
+}
+
+/// Look up every chain served by `list` in `registry`, in ascending order of
+/// identifier.
+pub fn join<'a, R>(list: &RpcProviders, registry: &'a R) -> Vec<ListedChain<'a>>
+where
+    R: Registry + ?Sized,
+{
+    list.chain_ids()
+        .map(|chain_id| ListedChain {
+            chain_id,
+            info: registry.chain(chain_id),
+        })
+        .collect()
+}
+
+/// Remove every chain from `list` for which `keep` returns false.
+///
+/// `keep` is called with each chain's identifier and its metadata from
+/// `registry`, if known.
+pub fn retain<R, F>(list: &mut RpcProviders, registry: &R, mut keep: F)
+where
+    R: Registry + ?Sized,
+    F: FnMut(u64, Option<&ChainInfo>) -> bool,
+{
+    for provider in list.providers_mut() {
+        provider
+            .chains
+            .retain(|chain| keep(chain.chain_id, registry.chain(chain.chain_id)));
+    }
+}
+
+/// Keep only the chains `registry` knows to be mainnets.
+pub fn mainnets_only<R>(list: &mut RpcProviders, registry: &R)
+where
+    R: Registry + ?Sized,
+{
+    retain(list, registry, |_, info| {
+        info.map(|i| !i.is_testnet()).unwrap_or(false)
+    });
+}
+
+/// Keep only the chains `registry` knows to be testnets.
+pub fn testnets_only<R>(list: &mut RpcProviders, registry: &R)
+where
+    R: Registry + ?Sized,
+{
+    retain(list, registry, |_, info| {
+        info.map(ChainInfo::is_testnet).unwrap_or(false)
+    });
+}
@@ -0,0 +1,553 @@
+//! Comparing two versions of a list.
+//!
+//! [`Diff::between`] compares two resolved lists and describes each
+//! difference as an [`Entry`]. A [`Diff`] can be shown to people using its
+//! [`Display`](fmt::Display) implementation, which redacts any
+//! [secrets](crate::secrets) in endpoints, or serialized as JSON.
+//!
+//! ## Example
+//!
+//! ```
+//! use eip5139::diff::Diff;
+//! # let previous = eip5139::RpcProviders::default();
+//! # let current = eip5139::RpcProviders::default();
+//!
+//! let diff = Diff::between(&previous, &current);
+//!
+//! println!("{}", diff);
+//! println!("{}", diff.to_json());
+//! ```
+
+use crate::secrets;
+use crate::{Provider, RpcProviders, Timestamp, Version};
+
+use serde::Serialize;
+
+use serde_json::Value;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// Which part of a list's [`Version`] increased.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum Bump {
+    /// The new version is older than the current one.
+    Downgrade,
+
+    /// The version is unchanged.
+    None,
+
+    /// Bug fixes only (including pre-release and build changes.)
+    Patch,
+
+    /// Backwards compatible changes.
+    Minor,
+
+    /// Backwards incompatible changes.
+    Major,
+}
+
+impl Bump {
+    /// Compare `previous` to `current`.
+    pub fn between(previous: &Version, current: &Version) -> Self {
+        let precedence = |v: &Version| semver::Version {
+            build: semver::BuildMetadata::EMPTY,
+            ..v.clone().into_semver()
+        };
+
+        if precedence(current) < precedence(previous) {
+            Self::Downgrade
+        } else if current.major != previous.major {
+            Self::Major
+        } else if current.minor != previous.minor {
+            Self::Minor
+        } else if current != previous {
+            Self::Patch
+        } else {
+            Self::None
+        }
+    }
+
+    /// Whether the user should agree to the new version before it is used.
+    ///
+    /// True for new major versions, and for downgrades, which may roll back
+    /// to an older major version (or undo a fix.)
+    pub fn requires_consent(self) -> bool {
+        matches!(self, Self::Major | Self::Downgrade)
+    }
+}
+
+impl fmt::Display for Bump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Downgrade => write!(f, "downgrade"),
+            Self::None => write!(f, "none"),
+            Self::Patch => write!(f, "patch"),
+            Self::Minor => write!(f, "minor"),
+            Self::Major => write!(f, "major"),
+        }
+    }
+}
+
+/// A single difference between two versions of a list.
+///
+/// Entries about a provider identify it by its key. Chains and endpoints of
+/// added or removed providers, and endpoints of added or removed chains,
+/// aren't listed separately.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum Entry {
+    /// The list's name changed.
+    #[non_exhaustive]
+    Name {
+        /// The name before.
+        previous: String,
+
+        /// The name after.
+        current: String,
+    },
+
+    /// The list's logo changed.
+    #[non_exhaustive]
+    Logo {
+        /// The logo before.
+        previous: Option<String>,
+
+        /// The logo after.
+        current: Option<String>,
+    },
+
+    /// The list's version changed.
+    #[non_exhaustive]
+    Version {
+        /// The version before.
+        previous: Version,
+
+        /// The version after.
+        current: Version,
+
+        /// Which part of the version increased.
+        bump: Bump,
+    },
+
+    /// The list's timestamp changed.
+    #[non_exhaustive]
+    Timestamp {
+        /// The timestamp before.
+        previous: Timestamp,
+
+        /// The timestamp after.
+        current: Timestamp,
+    },
+
+    /// A provider was added.
+    #[non_exhaustive]
+    ProviderAdded {
+        /// Key of the provider.
+        provider: String,
+    },
+
+    /// A provider was removed.
+    #[non_exhaustive]
+    ProviderRemoved {
+        /// Key of the provider.
+        provider: String,
+    },
+
+    /// A provider's name changed.
+    #[non_exhaustive]
+    ProviderRenamed {
+        /// Key of the provider.
+        provider: String,
+
+        /// The name before.
+        previous: String,
+
+        /// The name after.
+        current: String,
+    },
+
+    /// A provider's logo changed.
+    #[non_exhaustive]
+    ProviderLogo {
+        /// Key of the provider.
+        provider: String,
+
+        /// The logo before.
+        previous: Option<String>,
+
+        /// The logo after.
+        current: Option<String>,
+    },
+
+    /// A provider's priority changed.
+    #[non_exhaustive]
+    Priority {
+        /// Key of the provider.
+        provider: String,
+
+        /// The priority before.
+        previous: Option<u32>,
+
+        /// The priority after.
+        current: Option<u32>,
+    },
+
+    /// A provider started serving a chain.
+    #[non_exhaustive]
+    ChainAdded {
+        /// Key of the provider.
+        provider: String,
+
+        /// The chain.
+        #[serde(rename = "chainId")]
+        chain_id: u64,
+    },
+
+    /// A provider stopped serving a chain.
+    #[non_exhaustive]
+    ChainRemoved {
+        /// Key of the provider.
+        provider: String,
+
+        /// The chain.
+        #[serde(rename = "chainId")]
+        chain_id: u64,
+    },
+
+    /// An endpoint was added to a chain a provider already served.
+    #[non_exhaustive]
+    EndpointAdded {
+        /// Key of the provider.
+        provider: String,
+
+        /// Chain the endpoint serves.
+        #[serde(rename = "chainId")]
+        chain_id: u64,
+
+        /// The endpoint.
+        endpoint: String,
+    },
+
+    /// An endpoint was removed from a chain a provider still serves.
+    #[non_exhaustive]
+    EndpointRemoved {
+        /// Key of the provider.
+        provider: String,
+
+        /// Chain the endpoint served.
+        #[serde(rename = "chainId")]
+        chain_id: u64,
+
+        /// The endpoint.
+        endpoint: String,
+    },
+}
+
+impl Entry {
+    /// Key of the provider this entry is about, if any.
+    pub fn provider(&self) -> Option<&str> {
+        match self {
+            Self::Name { .. }
+            | Self::Logo { .. }
+            | Self::Version { .. }
+            | Self::Timestamp { .. } => None,
+            Self::ProviderAdded { provider }
+            | Self::ProviderRemoved { provider }
+            | Self::ProviderRenamed { provider, .. }
+            | Self::ProviderLogo { provider, .. }
+            | Self::Priority { provider, .. }
+            | Self::ChainAdded { provider, .. }
+            | Self::ChainRemoved { provider, .. }
+            | Self::EndpointAdded { provider, .. }
+            | Self::EndpointRemoved { provider, .. } => Some(provider),
+        }
+    }
+}
+
+/// Displays the value, or `none`.
+struct Maybe<'a, T>(&'a Option<T>);
+
+impl<'a, T> fmt::Display for Maybe<'a, T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(v) => write!(f, "{}", v),
+            None => write!(f, "none"),
+        }
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Name { previous, current } => {
+                write!(f, "~ name: {:?} -> {:?}", previous, current)
+            }
+            Self::Logo { previous, current } => {
+                write!(f, "~ logo: {} -> {}", Maybe(previous), Maybe(current))
+            }
+            Self::Version {
+                previous,
+                current,
+                bump,
+            } => write!(f, "~ version: {} -> {} ({})", previous, current, bump),
+            Self::Timestamp { previous, current } => {
+                write!(f, "~ timestamp: {} -> {}", previous, current)
+            }
+            Self::ProviderAdded { provider } => write!(f, "+ provider {}", provider),
+            Self::ProviderRemoved { provider } => write!(f, "- provider {}", provider),
+            Self::ProviderRenamed {
+                provider,
+                previous,
+                current,
+            } => write!(
+                f,
+                "~ provider {}: name {:?} -> {:?}",
+                provider, previous, current
+            ),
+            Self::ProviderLogo {
+                provider,
+                previous,
+                current,
+            } => write!(
+                f,
+                "~ provider {}: logo {} -> {}",
+                provider,
+                Maybe(previous),
+                Maybe(current)
+            ),
+            Self::Priority {
+                provider,
+                previous,
+                current,
+            } => write!(
+                f,
+                "~ provider {}: priority {} -> {}",
+                provider,
+                Maybe(previous),
+                Maybe(current)
+            ),
+            Self::ChainAdded { provider, chain_id } => {
+                write!(f, "+ provider {}: chain {}", provider, chain_id)
+            }
+            Self::ChainRemoved { provider, chain_id } => {
+                write!(f, "- provider {}: chain {}", provider, chain_id)
+            }
+            Self::EndpointAdded {
+                provider,
+                chain_id,
+                endpoint,
+            } => write!(
+                f,
+                "+ provider {}: chain {}: {}",
+                provider,
+                chain_id,
+                secrets::redact(endpoint)
+            ),
+            Self::EndpointRemoved {
+                provider,
+                chain_id,
+                endpoint,
+            } => write!(
+                f,
+                "- provider {}: chain {}: {}",
+                provider,
+                chain_id,
+                secrets::redact(endpoint)
+            ),
+        }
+    }
+}
+
+/// Every difference between two lists, in a stable order: list metadata
+/// first, then providers by key.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct Diff {
+    /// The differences.
+    pub entries: Vec<Entry>,
+}
+
+impl Diff {
+    /// Compare `previous` to `current`.
+    pub fn between(previous: &RpcProviders, current: &RpcProviders) -> Self {
+        let mut entries = Vec::new();
+
+        if previous.name != current.name {
+            entries.push(Entry::Name {
+                previous: previous.name.clone(),
+                current: current.name.clone(),
+            });
+        }
+
+        if previous.logo != current.logo {
+            entries.push(Entry::Logo {
+                previous: previous.logo.clone(),
+                current: current.logo.clone(),
+            });
+        }
+
+        let bump = Bump::between(previous.version(), current.version());
+        if bump != Bump::None {
+            entries.push(Entry::Version {
+                previous: previous.version().clone(),
+                current: current.version().clone(),
+                bump,
+            });
+        }
+
+        if previous.timestamp != current.timestamp {
+            entries.push(Entry::Timestamp {
+                previous: previous.timestamp.clone(),
+                current: current.timestamp.clone(),
+            });
+        }
+
+        let by_key = |list: &RpcProviders| -> BTreeMap<String, Provider> {
+            list.providers()
+                .iter()
+                .map(|p| (p.key.clone(), p.clone()))
+                .collect()
+        };
+
+        let before = by_key(previous);
+        let after = by_key(current);
+
+        let keys: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+
+        for key in keys {
+            match (before.get(key), after.get(key)) {
+                (Some(_), None) => entries.push(Entry::ProviderRemoved {
+                    provider: key.clone(),
+                }),
+                (None, Some(_)) => entries.push(Entry::ProviderAdded {
+                    provider: key.clone(),
+                }),
+                (Some(old), Some(new)) => provider(&mut entries, key, old, new),
+                (None, None) => unreachable!(),
+            }
+        }
+
+        Self { entries }
+    }
+
+    /// Whether the lists are the same.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Which part of the version increased, or [`Bump::None`].
+    pub fn bump(&self) -> Bump {
+        self.entries
+            .iter()
+            .find_map(|e| match e {
+                Entry::Version { bump, .. } => Some(*bump),
+                _ => None,
+            })
+            .unwrap_or(Bump::None)
+    }
+
+    /// The differences as a JSON array of objects, each with a `type`.
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(self).unwrap()
+    }
+}
+
+impl fmt::Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "{}", entry)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Endpoints of `provider`, by chain.
+fn chains(provider: &Provider) -> BTreeMap<u64, BTreeSet<&str>> {
+    let mut chains = BTreeMap::<u64, BTreeSet<&str>>::new();
+
+    for chain in &provider.chains {
+        chains
+            .entry(chain.chain_id)
+            .or_default()
+            .extend(chain.endpoints.iter().map(String::as_str));
+    }
+
+    chains
+}
+
+/// Compare two versions of the provider identified by `key`.
+fn provider(entries: &mut Vec<Entry>, key: &str, old: &Provider, new: &Provider) {
+    if old.name != new.name {
+        entries.push(Entry::ProviderRenamed {
+            provider: key.to_owned(),
+            previous: old.name.clone(),
+            current: new.name.clone(),
+        });
+    }
+
+    if old.logo != new.logo {
+        entries.push(Entry::ProviderLogo {
+            provider: key.to_owned(),
+            previous: old.logo.clone(),
+            current: new.logo.clone(),
+        });
+    }
+
+    if old.priority != new.priority {
+        entries.push(Entry::Priority {
+            provider: key.to_owned(),
+            previous: old.priority,
+            current: new.priority,
+        });
+    }
+
+    let before = chains(old);
+    let after = chains(new);
+
+    let ids: BTreeSet<u64> = before.keys().chain(after.keys()).copied().collect();
+
+    for chain_id in ids {
+        let (old, new) = match (before.get(&chain_id), after.get(&chain_id)) {
+            (Some(_), None) => {
+                entries.push(Entry::ChainRemoved {
+                    provider: key.to_owned(),
+                    chain_id,
+                });
+                continue;
+            }
+            (None, Some(_)) => {
+                entries.push(Entry::ChainAdded {
+                    provider: key.to_owned(),
+                    chain_id,
+                });
+                continue;
+            }
+            (Some(old), Some(new)) => (old, new),
+            (None, None) => unreachable!(),
+        };
+
+        for endpoint in old.difference(new) {
+            entries.push(Entry::EndpointRemoved {
+                provider: key.to_owned(),
+                chain_id,
+                endpoint: (*endpoint).to_owned(),
+            });
+        }
+
+        for endpoint in new.difference(old) {
+            entries.push(Entry::EndpointAdded {
+                provider: key.to_owned(),
+                chain_id,
+                endpoint: (*endpoint).to_owned(),
+            });
+        }
+    }
+}
@@ -0,0 +1,229 @@
+//! Parsed endpoint addresses.
+
+use crate::errors::EndpointError;
+use crate::secrets;
+
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use url::{Host, Url};
+
+/// The URI scheme of an [`Endpoint`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Scheme {
+    /// `http://`
+    Http,
+
+    /// `https://`
+    Https,
+
+    /// `ws://`
+    Ws,
+
+    /// `wss://`
+    Wss,
+
+    /// `ipc://`
+    Ipc,
+
+    /// Any other scheme, in lowercase.
+    Other(String),
+}
+
+impl Scheme {
+    fn new(scheme: &str) -> Self {
+        match scheme {
+            "http" => Self::Http,
+            "https" => Self::Https,
+            "ws" => Self::Ws,
+            "wss" => Self::Wss,
+            "ipc" => Self::Ipc,
+            other => Self::Other(other.to_owned()),
+        }
+    }
+
+    /// The scheme as written in a URI, in lowercase and without `://`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Http => "http",
+            Self::Https => "https",
+            Self::Ws => "ws",
+            Self::Wss => "wss",
+            Self::Ipc => "ipc",
+            Self::Other(other) => other,
+        }
+    }
+
+    /// The kind of connection used to reach an endpoint with this scheme, if
+    /// the scheme is recognized.
+    pub fn transport(&self) -> Option<Transport> {
+        match self {
+            Self::Http | Self::Https => Some(Transport::Http),
+            Self::Ws | Self::Wss => Some(Transport::WebSocket),
+            Self::Ipc => Some(Transport::Ipc),
+            Self::Other(_) => None,
+        }
+    }
+
+    /// Whether connections using this scheme are encrypted with TLS.
+    pub fn is_secure(&self) -> bool {
+        matches!(self, Self::Https | Self::Wss)
+    }
+}
+
+/// The kind of connection used to reach an [`Endpoint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Transport {
+    /// Request/response over HTTP or HTTPS.
+    Http,
+
+    /// A persistent WebSocket connection.
+    WebSocket,
+
+    /// Inter-process communication on the local machine.
+    Ipc,
+}
+
+/// An address serving the Ethereum JSON RPC interface.
+///
+/// ## Example
+///
+/// ```
+/// use eip5139::endpoint::{Endpoint, Scheme, Transport};
+///
+/// let endpoint: Endpoint = "wss://mainnet.example.com:8546/ws".parse().unwrap();
+///
+/// assert_eq!(endpoint.scheme(), Scheme::Wss);
+/// assert_eq!(endpoint.transport(), Some(Transport::WebSocket));
+/// assert_eq!(endpoint.host(), Some("mainnet.example.com"));
+/// assert_eq!(endpoint.port(), Some(8546));
+/// assert_eq!(endpoint.path(), "/ws");
+/// ```
+///
+/// The `Debug` output redacts secrets (see [`secrets`](crate::secrets).)
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Endpoint {
+    url: Url,
+}
+
+impl Endpoint {
+    /// Parse an endpoint from a URI.
+    pub fn parse(uri: &str) -> Result<Self, EndpointError> {
+        let url = Url::parse(uri).map_err(EndpointError)?;
+        Ok(Self { url })
+    }
+
+    /// The scheme of this endpoint.
+    pub fn scheme(&self) -> Scheme {
+        Scheme::new(self.url.scheme())
+    }
+
+    /// The kind of connection used to reach this endpoint, if the scheme is
+    /// recognized.
+    pub fn transport(&self) -> Option<Transport> {
+        self.scheme().transport()
+    }
+
+    /// Whether connections to this endpoint are encrypted with TLS.
+    pub fn is_secure(&self) -> bool {
+        self.scheme().is_secure()
+    }
+
+    /// The host of this endpoint, if any.
+    pub fn host(&self) -> Option<&str> {
+        self.url.host_str()
+    }
+
+    /// The IP address of this endpoint, if its host is an IP address instead
+    /// of a name.
+    pub fn ip(&self) -> Option<IpAddr> {
+        match self.url.host()? {
+            Host::Ipv4(ip) => Some(ip.into()),
+            Host::Ipv6(ip) => Some(ip.into()),
+            Host::Domain(_) => None,
+        }
+    }
+
+    /// The port of this endpoint, falling back to the default port for the
+    /// scheme.
+    pub fn port(&self) -> Option<u16> {
+        self.url.port_or_known_default()
+    }
+
+    /// The user name of this endpoint, or an empty string if there is none.
+    pub fn username(&self) -> &str {
+        self.url.username()
+    }
+
+    /// The password of this endpoint, if any.
+    pub fn password(&self) -> Option<&str> {
+        self.url.password()
+    }
+
+    /// The path of this endpoint.
+    pub fn path(&self) -> &str {
+        self.url.path()
+    }
+
+    /// The query string of this endpoint, if any.
+    pub fn query(&self) -> Option<&str> {
+        self.url.query()
+    }
+
+    /// The endpoint, serialized as a URI.
+    pub fn as_str(&self) -> &str {
+        self.url.as_str()
+    }
+
+    /// A canonical spelling of this endpoint, for comparing endpoints.
+    ///
+    /// The scheme and host are lowercased, default ports and fragments are
+    /// removed, and a trailing slash is removed from the path (an empty path
+    /// becomes `/`.)
+    ///
+    /// ```
+    /// use eip5139::Endpoint;
+    ///
+    /// let a = Endpoint::parse("HTTPS://Example.COM:443/rpc/").unwrap();
+    /// let b = Endpoint::parse("https://example.com/rpc").unwrap();
+    ///
+    /// assert_eq!(a.canonical(), b.canonical());
+    /// ```
+    pub fn canonical(&self) -> String {
+        let mut url = self.url.clone();
+        url.set_fragment(None);
+
+        let path = url.path();
+        if path.len() > 1 && path.ends_with('/') {
+            let trimmed = path.trim_end_matches('/').to_owned();
+            url.set_path(&trimmed);
+        }
+
+        url.into()
+    }
+}
+
+impl FromStr for Endpoint {
+    type Err = EndpointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Debug for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Endpoint")
+            .field(&secrets::redact(self.as_str()))
+            .finish()
+    }
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
@@ -1,7 +1,10 @@
 //! Error types returned by functions in this crate.
 
+use crate::secrets::{self, Redacted};
+
 use std::error::Error as StdError;
 use std::fmt::{self, Write};
+use std::time::Duration;
 
 #[derive(Debug)]
 pub(crate) enum InnerFetchError {
@@ -50,6 +53,200 @@ impl StdError for FetchError {
     }
 }
 
+/// Error type for [`Transport`](crate::transport::Transport) operations.
+#[derive(Debug)]
+pub struct TransportError {
+    inner: Box<dyn StdError + 'static>,
+}
+
+impl TransportError {
+    /// Create a [`TransportError`] from a source [`Error`](std::error::Error).
+    pub fn custom<E>(err: E) -> Self
+    where
+        E: 'static + StdError,
+    {
+        Self {
+            inner: Box::new(err),
+        }
+    }
+}
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.inner)
+    }
+}
+
+impl StdError for TransportError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(Box::as_ref(&self.inner))
+    }
+}
+
+/// Type representing errors encountered while calling a JSON-RPC method.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RpcError {
+    /// The request could not be delivered, or no response was received.
+    #[non_exhaustive]
+    Transport {
+        /// The underlying cause of this error.
+        source: TransportError,
+    },
+
+    /// The endpoint responded with a JSON-RPC error object.
+    #[non_exhaustive]
+    Response {
+        /// The JSON-RPC error code.
+        code: i64,
+
+        /// The JSON-RPC error message.
+        message: String,
+    },
+
+    /// The endpoint's response wasn't a valid JSON-RPC response, or its
+    /// result wasn't of the expected type.
+    #[non_exhaustive]
+    Malformed {
+        /// Description of what was wrong with the response.
+        message: String,
+    },
+
+    /// The endpoint wasn't called, because its circuit breaker is open.
+    #[non_exhaustive]
+    CircuitOpen {
+        /// How long until the endpoint will be tried again.
+        retry_after: Duration,
+    },
+}
+
+impl RpcError {
+    pub(crate) fn malformed<M>(message: M) -> Self
+    where
+        M: Into<String>,
+    {
+        Self::Malformed {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for RpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transport { source } => write!(f, "transport failed: {}", source),
+            Self::Response { code, message } => {
+                write!(f, "endpoint returned error {}: {}", code, message)
+            }
+            Self::Malformed { message } => write!(f, "malformed response: {}", message),
+            Self::CircuitOpen { retry_after } => {
+                write!(f, "circuit breaker open for {:?}", retry_after)
+            }
+        }
+    }
+}
+
+impl StdError for RpcError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::Transport { source } => Some(source),
+            Self::Response { .. } => None,
+            Self::Malformed { .. } => None,
+            Self::CircuitOpen { .. } => None,
+        }
+    }
+}
+
+impl From<TransportError> for RpcError {
+    fn from(source: TransportError) -> Self {
+        Self::Transport { source }
+    }
+}
+
+/// Type representing errors returned by a
+/// [`FailoverClient`](crate::failover::FailoverClient).
+#[non_exhaustive]
+pub enum FailoverError {
+    /// No provider in the list serves the requested chain.
+    #[non_exhaustive]
+    NoEndpoints {
+        /// The requested chain.
+        chain_id: u64,
+    },
+
+    /// An endpoint responded with an error that doesn't warrant trying
+    /// another endpoint.
+    #[non_exhaustive]
+    Rpc {
+        /// The endpoint that responded.
+        endpoint: String,
+
+        /// The underlying cause of this error.
+        source: RpcError,
+    },
+
+    /// Every available endpoint failed, or was skipped by its circuit breaker.
+    #[non_exhaustive]
+    Exhausted {
+        /// Each endpoint, and the reason it failed or
+        /// ([`RpcError::CircuitOpen`]) was skipped.
+        attempts: Vec<(String, RpcError)>,
+    },
+}
+
+impl fmt::Debug for FailoverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoEndpoints { chain_id } => f
+                .debug_struct("NoEndpoints")
+                .field("chain_id", chain_id)
+                .finish(),
+            Self::Rpc { endpoint, source } => f
+                .debug_struct("Rpc")
+                .field("endpoint", &Redacted(endpoint))
+                .field("source", source)
+                .finish(),
+            Self::Exhausted { attempts } => {
+                let attempts: Vec<_> = attempts
+                    .iter()
+                    .map(|(endpoint, error)| (Redacted(endpoint), error))
+                    .collect();
+                f.debug_struct("Exhausted")
+                    .field("attempts", &attempts)
+                    .finish()
+            }
+        }
+    }
+}
+
+impl fmt::Display for FailoverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoEndpoints { chain_id } => write!(f, "no endpoints for chain {}", chain_id),
+            Self::Rpc { endpoint, source } => {
+                write!(f, "request to {} failed: {}", Redacted(endpoint), source)
+            }
+            Self::Exhausted { attempts } => {
+                write!(f, "all endpoints failed")?;
+                for (endpoint, error) in attempts {
+                    write!(f, "; {}: {}", Redacted(endpoint), error)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl StdError for FailoverError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::NoEndpoints { .. } => None,
+            Self::Rpc { source, .. } => Some(source),
+            Self::Exhausted { .. } => None,
+        }
+    }
+}
+
 /// Type representing JSON Schema validation errors.
 #[derive(Debug)]
 pub struct ValidationError {
@@ -64,6 +261,16 @@ impl fmt::Display for ValidationError {
 
 impl StdError for ValidationError {}
 
+/// Every string in `value`, including those nested in arrays and objects.
+fn strings(value: &serde_json::Value) -> Vec<&String> {
+    match value {
+        serde_json::Value::String(s) => vec![s],
+        serde_json::Value::Array(items) => items.iter().flat_map(strings).collect(),
+        serde_json::Value::Object(members) => members.values().flat_map(strings).collect(),
+        _ => Vec::new(),
+    }
+}
+
 impl ValidationError {
     pub(crate) fn new<'a, I>(iter: I) -> Self
     where
@@ -71,7 +278,22 @@ impl ValidationError {
     {
         let mut message = String::new();
         for item in iter {
-            writeln!(message, "{}", item).unwrap();
+            // Invalid values are quoted in the message, so redact any
+            // endpoints among them.
+            let mut text = item.to_string();
+            for string in strings(&item.instance) {
+                let redacted = secrets::redact(string);
+                if redacted != *string {
+                    text = text.replace(string.as_str(), &redacted);
+                }
+            }
+
+            let path = item.instance_path.to_string();
+            if path.is_empty() {
+                writeln!(message, "{}", text).unwrap();
+            } else {
+                writeln!(message, "{}: {}", path, text).unwrap();
+            }
         }
         Self { message }
     }
@@ -109,9 +331,173 @@ impl StdError for PatchError {
     }
 }
 
-/// Type representing errors encountered while fetching an [`RpcProviders`](crate::RpcProviders).
+/// Type representing errors encountered while parsing an
+/// [`Endpoint`](crate::endpoint::Endpoint).
+#[derive(Debug)]
+pub struct EndpointError(pub(crate) url::ParseError);
+
+impl fmt::Display for EndpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl StdError for EndpointError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.0)
+    }
+}
+
+/// Type representing errors encountered while parsing a
+/// [`Timestamp`](crate::Timestamp).
+#[derive(Debug)]
+pub struct TimestampError(pub(crate) time::error::Parse);
+
+impl fmt::Display for TimestampError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl StdError for TimestampError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.0)
+    }
+}
+
+/// Type representing problems with the signature of a list. See
+/// [`signature`](crate::signature).
 #[derive(Debug)]
 #[non_exhaustive]
+pub enum SignatureError {
+    /// The signature, address or key couldn't be decoded.
+    #[non_exhaustive]
+    Malformed {
+        /// Description of what was wrong.
+        message: String,
+    },
+
+    /// The signature doesn't match the list, or (for ed25519) any trusted
+    /// key.
+    Invalid,
+
+    /// The list was signed by a signer that isn't trusted for its source.
+    #[non_exhaustive]
+    Untrusted {
+        /// The signer, formatted as a [`Signer`](crate::signature::Signer).
+        signer: String,
+    },
+}
+
+impl SignatureError {
+    pub(crate) fn malformed<M>(message: M) -> Self
+    where
+        M: Into<String>,
+    {
+        Self::Malformed {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed { message } => write!(f, "malformed signature: {}", message),
+            Self::Invalid => write!(f, "signature does not match"),
+            Self::Untrusted { signer } => write!(f, "signed by untrusted signer {}", signer),
+        }
+    }
+}
+
+impl StdError for SignatureError {}
+
+/// Type representing why a [`SourcePolicy`](crate::policy::SourcePolicy)
+/// rejected a source.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PolicyError {
+    /// The URI's scheme isn't allowed.
+    #[non_exhaustive]
+    Scheme {
+        /// The rejected scheme (`ens` for ENS names.)
+        scheme: String,
+    },
+
+    /// The URI's host is denied, or isn't allowed.
+    #[non_exhaustive]
+    Host {
+        /// The rejected host.
+        host: String,
+    },
+
+    /// The ENS name isn't allowed.
+    #[non_exhaustive]
+    Ens {
+        /// The rejected name.
+        name: String,
+    },
+
+    /// The URI is addressed to a private or otherwise non-public IP address.
+    #[non_exhaustive]
+    PrivateAddress {
+        /// The rejected address.
+        address: std::net::IpAddr,
+    },
+
+    /// The URI couldn't be parsed.
+    #[non_exhaustive]
+    Malformed {
+        /// Description of what was wrong.
+        message: String,
+    },
+
+    /// Rejected by a custom policy.
+    #[non_exhaustive]
+    Custom {
+        /// The underlying cause of this error.
+        source: Box<dyn StdError + 'static>,
+    },
+}
+
+impl PolicyError {
+    /// Create a [`PolicyError`] from a source [`Error`](std::error::Error).
+    pub fn custom<E>(err: E) -> Self
+    where
+        E: 'static + StdError,
+    {
+        Self::Custom {
+            source: Box::new(err),
+        }
+    }
+}
+
+impl fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Scheme { scheme } => write!(f, "scheme `{}` not allowed", scheme),
+            Self::Host { host } => write!(f, "host `{}` not allowed", host),
+            Self::Ens { name } => write!(f, "ENS name `{}` not allowed", name),
+            Self::PrivateAddress { address } => {
+                write!(f, "private address `{}` not allowed", address)
+            }
+            Self::Malformed { message } => write!(f, "malformed source: {}", message),
+            Self::Custom { source } => write!(f, "{}", source),
+        }
+    }
+}
+
+impl StdError for PolicyError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::Custom { source } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// Type representing errors encountered while fetching an [`RpcProviders`](crate::RpcProviders).
+#[non_exhaustive]
 pub enum Error {
     /// An error encountered while fetching a resource.
     #[non_exhaustive]
@@ -125,6 +511,10 @@ pub enum Error {
     Cycle {
         /// The source which was requested twice.
         duplicate: crate::Source,
+
+        /// The sources retrieved before `duplicate` was requested again,
+        /// starting with the requested list.
+        chain: Vec<crate::Source>,
     },
 
     /// An error encountered while parsing JSON.
@@ -150,23 +540,287 @@ pub enum Error {
 
     /// The chain of extension lists was too long.
     #[non_exhaustive]
-    TooDeep {},
+    TooDeep {
+        /// The sources requested before giving up, starting with the
+        /// requested list.
+        chain: Vec<crate::Source>,
+
+        /// The maximum number of extension lists allowed.
+        limit: usize,
+    },
 
     /// An extension list requested a parent with an incompatible version.
     #[non_exhaustive]
-    VersionMismatch {},
+    VersionMismatch {
+        /// The extension list that made the request.
+        child: crate::Source,
+
+        /// The range of versions requested by the extension list that the
+        /// parent did not satisfy.
+        requested: Box<crate::VersionRange>,
+
+        /// The parent list that was retrieved.
+        parent: crate::Source,
+
+        /// The version of the parent list.
+        actual: Box<crate::Version>,
+    },
+
+    /// A provider listed an endpoint that isn't a valid URI.
+    #[non_exhaustive]
+    Endpoint {
+        /// The endpoint, as it appeared in the list.
+        endpoint: String,
+
+        /// The underlying cause of this error.
+        source: EndpointError,
+    },
+
+    /// An extension list was timestamped before its parent list.
+    #[non_exhaustive]
+    TimestampOrder {
+        /// The extension list.
+        child: crate::Source,
+
+        /// The timestamp of the extension list.
+        timestamp: Box<crate::Timestamp>,
+
+        /// The parent list.
+        parent: crate::Source,
+
+        /// The timestamp of the parent list.
+        parent_timestamp: Box<crate::Timestamp>,
+    },
+
+    /// A newer version of a list was timestamped before the version it
+    /// replaces. See [`RpcProviders::check_update`](crate::RpcProviders::check_update).
+    #[non_exhaustive]
+    TimestampRegression {
+        /// The version being replaced.
+        previous: Box<crate::Version>,
+
+        /// The timestamp of the version being replaced.
+        previous_timestamp: Box<crate::Timestamp>,
+
+        /// The newer version.
+        version: Box<crate::Version>,
+
+        /// The timestamp of the newer version.
+        timestamp: Box<crate::Timestamp>,
+    },
+
+    /// A list had no signature, but a
+    /// [`TrustPolicy`](crate::signature::TrustPolicy) was in effect.
+    #[non_exhaustive]
+    Unsigned {
+        /// The unsigned list.
+        list: crate::Source,
+    },
+
+    /// A list's signature wasn't made by a trusted signer.
+    #[non_exhaustive]
+    BadSignature {
+        /// The list.
+        list: crate::Source,
+
+        /// The underlying cause of this error.
+        source: SignatureError,
+    },
+
+    /// A [`SourcePolicy`](crate::policy::SourcePolicy) rejected a list's
+    /// source before it was retrieved.
+    #[non_exhaustive]
+    Policy {
+        /// The rejected source.
+        list: crate::Source,
+
+        /// The underlying cause of this error.
+        source: PolicyError,
+    },
+}
+
+impl Error {
+    /// A stable, machine-readable code identifying the kind of this error.
+    ///
+    /// Unlike the `Display` output, codes never change between releases. The
+    /// same codes are exposed to JavaScript as the `code` property of thrown
+    /// errors.
+    ///
+    /// | Variant                                            | Code                   |
+    /// |----------------------------------------------------|------------------------|
+    /// | [`Fetch`](Self::Fetch)                             | `FETCH`                |
+    /// | [`Cycle`](Self::Cycle)                             | `CYCLE`                |
+    /// | [`Json`](Self::Json)                               | `JSON`                 |
+    /// | [`Patch`](Self::Patch)                             | `PATCH`                |
+    /// | [`Validation`](Self::Validation)                   | `VALIDATION`           |
+    /// | [`TooDeep`](Self::TooDeep)                         | `TOO_DEEP`             |
+    /// | [`VersionMismatch`](Self::VersionMismatch)         | `VERSION_MISMATCH`     |
+    /// | [`Endpoint`](Self::Endpoint)                       | `ENDPOINT`             |
+    /// | [`TimestampOrder`](Self::TimestampOrder)           | `TIMESTAMP_ORDER`      |
+    /// | [`TimestampRegression`](Self::TimestampRegression) | `TIMESTAMP_REGRESSION` |
+    /// | [`Unsigned`](Self::Unsigned)                       | `UNSIGNED`             |
+    /// | [`BadSignature`](Self::BadSignature)               | `BAD_SIGNATURE`        |
+    /// | [`Policy`](Self::Policy)                           | `POLICY`               |
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Fetch { .. } => "FETCH",
+            Self::Cycle { .. } => "CYCLE",
+            Self::Json { .. } => "JSON",
+            Self::Patch { .. } => "PATCH",
+            Self::Validation { .. } => "VALIDATION",
+            Self::TooDeep { .. } => "TOO_DEEP",
+            Self::VersionMismatch { .. } => "VERSION_MISMATCH",
+            Self::Endpoint { .. } => "ENDPOINT",
+            Self::TimestampOrder { .. } => "TIMESTAMP_ORDER",
+            Self::TimestampRegression { .. } => "TIMESTAMP_REGRESSION",
+            Self::Unsigned { .. } => "UNSIGNED",
+            Self::BadSignature { .. } => "BAD_SIGNATURE",
+            Self::Policy { .. } => "POLICY",
+        }
+    }
+}
+
+/// Like a derived implementation, but with endpoints redacted.
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fetch { source } => f.debug_struct("Fetch").field("source", source).finish(),
+            Self::Cycle { duplicate, chain } => f
+                .debug_struct("Cycle")
+                .field("duplicate", duplicate)
+                .field("chain", chain)
+                .finish(),
+            Self::Json { source } => f.debug_struct("Json").field("source", source).finish(),
+            Self::Patch { source } => f.debug_struct("Patch").field("source", source).finish(),
+            Self::Validation { source } => f
+                .debug_struct("Validation")
+                .field("source", source)
+                .finish(),
+            Self::TooDeep { chain, limit } => f
+                .debug_struct("TooDeep")
+                .field("chain", chain)
+                .field("limit", limit)
+                .finish(),
+            Self::VersionMismatch {
+                child,
+                requested,
+                parent,
+                actual,
+            } => f
+                .debug_struct("VersionMismatch")
+                .field("child", child)
+                .field("requested", requested)
+                .field("parent", parent)
+                .field("actual", actual)
+                .finish(),
+            Self::Endpoint { endpoint, source } => f
+                .debug_struct("Endpoint")
+                .field("endpoint", &Redacted(endpoint))
+                .field("source", source)
+                .finish(),
+            Self::TimestampOrder {
+                child,
+                timestamp,
+                parent,
+                parent_timestamp,
+            } => f
+                .debug_struct("TimestampOrder")
+                .field("child", child)
+                .field("timestamp", timestamp)
+                .field("parent", parent)
+                .field("parent_timestamp", parent_timestamp)
+                .finish(),
+            Self::TimestampRegression {
+                previous,
+                previous_timestamp,
+                version,
+                timestamp,
+            } => f
+                .debug_struct("TimestampRegression")
+                .field("previous", previous)
+                .field("previous_timestamp", previous_timestamp)
+                .field("version", version)
+                .field("timestamp", timestamp)
+                .finish(),
+            Self::Unsigned { list } => f.debug_struct("Unsigned").field("list", list).finish(),
+            Self::BadSignature { list, source } => f
+                .debug_struct("BadSignature")
+                .field("list", list)
+                .field("source", source)
+                .finish(),
+            Self::Policy { list, source } => f
+                .debug_struct("Policy")
+                .field("list", list)
+                .field("source", source)
+                .finish(),
+        }
+    }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Fetch { source } => write!(f, "fetch failed: {}", source),
-            Self::Cycle { duplicate } => write!(f, "cycle detected at: {:?}", duplicate),
+            Self::Cycle { duplicate, chain } => {
+                write!(f, "cycle detected: ")?;
+                for source in chain {
+                    write!(f, "{} -> ", source)?;
+                }
+                write!(f, "{}", duplicate)
+            }
             Self::Json { source } => write!(f, "parsing json failed: {}", source),
             Self::Patch { source } => write!(f, "applying patch failed: {}", source),
             Self::Validation { source } => write!(f, "schema validation failed: {}", source),
-            Self::TooDeep { .. } => write!(f, "too many extension lists"),
-            Self::VersionMismatch { .. } => write!(f, "parent list not compatible with child"),
+            Self::TooDeep { chain, limit } => {
+                write!(f, "too many extension lists (limit {}): ", limit)?;
+                for (idx, source) in chain.iter().enumerate() {
+                    if idx != 0 {
+                        write!(f, " -> ")?;
+                    }
+                    write!(f, "{}", source)?;
+                }
+                Ok(())
+            }
+            Self::VersionMismatch {
+                child,
+                requested,
+                parent,
+                actual,
+            } => write!(
+                f,
+                "parent list not compatible with child: {} requires {} of {}, found {}",
+                child, requested, parent, actual
+            ),
+            Self::Endpoint { endpoint, source } => {
+                write!(f, "invalid endpoint `{}`: {}", Redacted(endpoint), source)
+            }
+            Self::TimestampOrder {
+                child,
+                timestamp,
+                parent,
+                parent_timestamp,
+            } => write!(
+                f,
+                "extension list older than parent: {} is from {}, but {} is from {}",
+                child, timestamp, parent, parent_timestamp
+            ),
+            Self::TimestampRegression {
+                previous,
+                previous_timestamp,
+                version,
+                timestamp,
+            } => write!(
+                f,
+                "newer version older than previous: {} is from {}, but {} is from {}",
+                version, timestamp, previous, previous_timestamp
+            ),
+            Self::Unsigned { list } => write!(f, "list is not signed: {}", list),
+            Self::BadSignature { list, source } => {
+                write!(f, "bad signature for {}: {}", list, source)
+            }
+            Self::Policy { list, source } => {
+                write!(f, "source rejected by policy: {}: {}", list, source)
+            }
         }
     }
 }
@@ -181,6 +835,12 @@ impl StdError for Error {
             Self::Validation { source } => Some(source),
             Self::TooDeep { .. } => None,
             Self::VersionMismatch { .. } => None,
+            Self::Endpoint { source, .. } => Some(source),
+            Self::TimestampOrder { .. } => None,
+            Self::TimestampRegression { .. } => None,
+            Self::Unsigned { .. } => None,
+            Self::BadSignature { source, .. } => Some(source),
+            Self::Policy { source, .. } => Some(source),
         }
     }
 }
@@ -0,0 +1,148 @@
+//! Structured events emitted while resolving an [`RpcProviders`](crate::RpcProviders).
+//!
+//! ## Example
+//!
+//! ```
+//! use eip5139::events::Event;
+//! use eip5139::Resolver;
+//! # use eip5139::{Fetch, Source};
+//! # use eip5139::errors::FetchError;
+//! # use std::future::Future;
+//! # use std::pin::Pin;
+//! # struct NoFetch;
+//! # impl Fetch for NoFetch {
+//! #     fn fetch(&mut self, _: Source) ->
+//! #         Pin<Box<dyn Future<Output = Result<String, FetchError>>>>
+//! #     {
+//! #         unimplemented!()
+//! #     }
+//! # }
+//! # let fetch = NoFetch;
+//!
+//! let resolver = Resolver::new(fetch).observer(|event: &Event<'_>| {
+//!     if let Event::FetchFinished { source, elapsed, .. } = event {
+//!         eprintln!("fetched {:?} in {:?}", source, elapsed);
+//!     }
+//! });
+//! ```
+
+use crate::Source;
+
+use std::time::Duration;
+
+/// A single step taken while resolving a list.
+///
+/// Every event carries the [`Source`] of the list it concerns. Events that
+/// mark the end of a step also carry the time that step took.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum Event<'a> {
+    /// A list is about to be retrieved.
+    #[non_exhaustive]
+    FetchStarted {
+        /// Location of the list being retrieved.
+        source: &'a Source,
+    },
+
+    /// A list was retrieved successfully.
+    #[non_exhaustive]
+    FetchFinished {
+        /// Location of the retrieved list.
+        source: &'a Source,
+
+        /// Time spent retrieving the list.
+        elapsed: Duration,
+    },
+
+    /// A list's signature was made by a trusted signer.
+    ///
+    /// Only emitted when resolving with a
+    /// [`TrustPolicy`](crate::signature::TrustPolicy).
+    #[non_exhaustive]
+    SignatureVerified {
+        /// Location of the signed list.
+        source: &'a Source,
+
+        /// Time spent retrieving and checking the signature.
+        elapsed: Duration,
+    },
+
+    /// A list passed JSON Schema validation.
+    ///
+    /// Emitted once for every retrieved list, and again for the result of
+    /// applying each extension list's changes.
+    #[non_exhaustive]
+    Validated {
+        /// Location of the validated list.
+        source: &'a Source,
+
+        /// Time spent validating the list.
+        elapsed: Duration,
+    },
+
+    /// An extension list's version requirement was satisfied by its parent.
+    #[non_exhaustive]
+    VersionChecked {
+        /// Location of the extension list.
+        source: &'a Source,
+
+        /// Location of the parent list.
+        parent: &'a Source,
+
+        /// Time spent checking the version.
+        elapsed: Duration,
+    },
+
+    /// An extension list's changes were applied to its parent.
+    #[non_exhaustive]
+    PatchApplied {
+        /// Location of the extension list.
+        source: &'a Source,
+
+        /// Time spent applying the changes.
+        elapsed: Duration,
+    },
+}
+
+impl<'a> Event<'a> {
+    /// Location of the list this event concerns.
+    pub fn source(&self) -> &'a Source {
+        match self {
+            Self::FetchStarted { source }
+            | Self::FetchFinished { source, .. }
+            | Self::SignatureVerified { source, .. }
+            | Self::Validated { source, .. }
+            | Self::VersionChecked { source, .. }
+            | Self::PatchApplied { source, .. } => source,
+        }
+    }
+
+    /// Time taken by the step this event marks the end of, if any.
+    pub fn elapsed(&self) -> Option<Duration> {
+        match self {
+            Self::FetchStarted { .. } => None,
+            Self::FetchFinished { elapsed, .. }
+            | Self::SignatureVerified { elapsed, .. }
+            | Self::Validated { elapsed, .. }
+            | Self::VersionChecked { elapsed, .. }
+            | Self::PatchApplied { elapsed, .. } => Some(*elapsed),
+        }
+    }
+}
+
+/// Receives [`Event`]s emitted during resolution.
+///
+/// Implemented for any `FnMut(&Event<'_>)` closure.
+pub trait Observer {
+    /// Called synchronously as each step of resolution happens.
+    fn observe(&mut self, event: &Event<'_>);
+}
+
+impl<F> Observer for F
+where
+    F: FnMut(&Event<'_>),
+{
+    fn observe(&mut self, event: &Event<'_>) {
+        self(event)
+    }
+}
@@ -0,0 +1,281 @@
+//! Dispatching JSON-RPC requests with automatic failover between endpoints.
+//!
+//! A [`FailoverClient`] sends each request to the endpoints for one chain in
+//! priority order, moving on when an endpoint can't be reached or responds
+//! with a retryable error. Each endpoint has a circuit breaker, so endpoints
+//! that keep failing are skipped until a cooldown has passed.
+//!
+//! ## Example
+//!
+//! ```
+//! use eip5139::failover::FailoverClient;
+//!
+//! use serde_json::json;
+//! # use eip5139::RpcProviders;
+//! # use eip5139::errors::TransportError;
+//! # use eip5139::transport::Transport;
+//! # use serde_json::Value;
+//! # use std::future::Future;
+//! # use std::pin::Pin;
+//! # struct NoTransport;
+//! # impl Transport for NoTransport {
+//! #     fn send(&mut self, _: &str, _: Value) ->
+//! #         Pin<Box<dyn Future<Output = Result<Value, TransportError>>>>
+//! #     {
+//! #         unimplemented!()
+//! #     }
+//! # }
+//! # let transport = NoTransport;
+//! # let list = RpcProviders::default();
+//!
+//! let mut client = FailoverClient::new(transport, &list, 137);
+//!
+//! let future = async move {
+//!     let block = client.request("eth_blockNumber", json!([])).await;
+//! };
+//! ```
+
+use crate::errors::{FailoverError, RpcError};
+use crate::secrets::Redacted;
+use crate::timer::Timer;
+use crate::transport::{self, Transport};
+use crate::RpcProviders;
+
+use serde_json::Value;
+
+use std::collections::HashSet;
+use std::fmt;
+use std::time::Duration;
+
+/// JSON-RPC error codes that are retried on another endpoint by default.
+///
+/// These are the "internal error" code from JSON-RPC 2.0, and the "resource
+/// unavailable" and "limit exceeded" codes from [EIP-1474].
+///
+/// [EIP-1474]: https://eips.ethereum.org/EIPS/eip-1474
+pub const DEFAULT_RETRY_CODES: &[i64] = &[-32603, -32002, -32005];
+
+/// State of an endpoint's circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BreakerState {
+    /// Requests are sent to the endpoint.
+    Closed,
+
+    /// The endpoint failed too many times in a row, and is skipped until the
+    /// cooldown has passed.
+    Open,
+
+    /// The cooldown has passed, and the next request is a trial. Success
+    /// closes the breaker, and failure opens it again.
+    HalfOpen,
+}
+
+/// Request statistics for one endpoint.
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct EndpointStats {
+    /// Key of the provider listing this endpoint.
+    pub provider: String,
+
+    /// The address of the endpoint.
+    pub endpoint: String,
+
+    /// Number of requests sent to this endpoint.
+    pub requests: u64,
+
+    /// Number of requests that succeeded, or failed with a non-retryable
+    /// error.
+    pub successes: u64,
+
+    /// Number of requests that failed with a transport or retryable error.
+    pub failures: u64,
+
+    /// Round trip time of the most recent request.
+    pub last_latency: Option<Duration>,
+
+    /// Current state of the circuit breaker.
+    pub state: BreakerState,
+}
+
+impl fmt::Debug for EndpointStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EndpointStats")
+            .field("provider", &self.provider)
+            .field("endpoint", &Redacted(&self.endpoint))
+            .field("requests", &self.requests)
+            .field("successes", &self.successes)
+            .field("failures", &self.failures)
+            .field("last_latency", &self.last_latency)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    stats: EndpointStats,
+    consecutive_failures: u32,
+    opened: Option<Timer>,
+}
+
+/// Sends JSON-RPC requests for one chain, failing over between endpoints.
+#[derive(Debug)]
+pub struct FailoverClient<T> {
+    transport: T,
+    chain_id: u64,
+    endpoints: Vec<Endpoint>,
+    retry_codes: HashSet<i64>,
+    failure_threshold: u32,
+    cooldown: Duration,
+}
+
+impl<T> FailoverClient<T>
+where
+    T: Transport,
+{
+    /// Create a client for the endpoints serving `chain_id` in `providers`,
+    /// sending requests through `transport`.
+    ///
+    /// Endpoints are tried in provider priority order. The endpoints are
+    /// copied, so later changes to `providers` have no effect.
+    pub fn new(transport: T, providers: &RpcProviders, chain_id: u64) -> Self {
+        let endpoints = providers
+            .endpoints_for(chain_id)
+            .map(|e| Endpoint {
+                stats: EndpointStats {
+                    provider: e.provider.key.clone(),
+                    endpoint: e.endpoint.to_owned(),
+                    requests: 0,
+                    successes: 0,
+                    failures: 0,
+                    last_latency: None,
+                    state: BreakerState::Closed,
+                },
+                consecutive_failures: 0,
+                opened: None,
+            })
+            .collect();
+
+        Self {
+            transport,
+            chain_id,
+            endpoints,
+            retry_codes: DEFAULT_RETRY_CODES.iter().copied().collect(),
+            failure_threshold: 3,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+
+    /// Retry on another endpoint when a JSON-RPC error with one of `codes`
+    /// is returned, instead of [`DEFAULT_RETRY_CODES`].
+    pub fn retry_codes<I>(mut self, codes: I) -> Self
+    where
+        I: IntoIterator<Item = i64>,
+    {
+        self.retry_codes = codes.into_iter().collect();
+        self
+    }
+
+    /// Open an endpoint's circuit breaker after `failures` consecutive
+    /// failures. Defaults to 3.
+    pub fn failure_threshold(mut self, failures: u32) -> Self {
+        self.failure_threshold = failures.max(1);
+        self
+    }
+
+    /// Skip an endpoint for `cooldown` after its circuit breaker opens.
+    /// Defaults to 30 seconds.
+    pub fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Get the chain this client sends requests to.
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// Get the statistics for each endpoint, in the order they're tried.
+    pub fn stats(&self) -> impl Iterator<Item = &EndpointStats> {
+        self.endpoints.iter().map(|e| &e.stats)
+    }
+
+    fn is_retryable(&self, error: &RpcError) -> bool {
+        match error {
+            RpcError::Response { code, .. } => self.retry_codes.contains(code),
+            _ => true,
+        }
+    }
+
+    /// Call `method` with `params`, returning the result from the first
+    /// endpoint that succeeds.
+    pub async fn request(&mut self, method: &str, params: Value) -> Result<Value, FailoverError> {
+        if self.endpoints.is_empty() {
+            return Err(FailoverError::NoEndpoints {
+                chain_id: self.chain_id,
+            });
+        }
+
+        let mut attempts = Vec::new();
+
+        for idx in 0..self.endpoints.len() {
+            let endpoint = &mut self.endpoints[idx];
+
+            if endpoint.stats.state == BreakerState::Open {
+                match endpoint.opened.map(|opened| opened.elapsed()) {
+                    Some(elapsed) if elapsed < self.cooldown => {
+                        let retry_after = self.cooldown - elapsed;
+                        attempts.push((
+                            endpoint.stats.endpoint.clone(),
+                            RpcError::CircuitOpen { retry_after },
+                        ));
+                        continue;
+                    }
+                    _ => endpoint.stats.state = BreakerState::HalfOpen,
+                }
+            }
+
+            endpoint.stats.requests += 1;
+            let address = endpoint.stats.endpoint.clone();
+
+            let timer = Timer::start();
+            let result =
+                transport::call(&mut self.transport, &address, method, params.clone()).await;
+            let latency = timer.elapsed();
+
+            let retry = match result {
+                Err(ref error) => self.is_retryable(error),
+                Ok(_) => false,
+            };
+
+            let endpoint = &mut self.endpoints[idx];
+            endpoint.stats.last_latency = Some(latency);
+
+            if !retry {
+                endpoint.stats.successes += 1;
+                endpoint.stats.state = BreakerState::Closed;
+                endpoint.consecutive_failures = 0;
+                endpoint.opened = None;
+
+                return result.map_err(|source| FailoverError::Rpc {
+                    endpoint: address,
+                    source,
+                });
+            }
+
+            endpoint.stats.failures += 1;
+            endpoint.consecutive_failures += 1;
+
+            let trial_failed = endpoint.stats.state == BreakerState::HalfOpen;
+            if trial_failed || endpoint.consecutive_failures >= self.failure_threshold {
+                endpoint.stats.state = BreakerState::Open;
+                endpoint.opened = Some(Timer::start());
+            }
+
+            attempts.push((address, result.unwrap_err()));
+        }
+
+        Err(FailoverError::Exhausted { attempts })
+    }
+}
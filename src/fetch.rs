@@ -53,4 +53,37 @@ pub trait Fetch {
         &mut self,
         source: crate::Source,
     ) -> Pin<Box<dyn Future<Output = Result<String, FetchError>>>>;
+
+    /// Retrieve the detached signature of the list located by `source`, or
+    /// `None` if it isn't signed.
+    ///
+    /// Only called when resolving with a
+    /// [`TrustPolicy`](crate::signature::TrustPolicy). The default
+    /// implementation returns `None`.
+    fn fetch_signature(
+        &mut self,
+        source: crate::Source,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<String>, FetchError>>>> {
+        let _ = source;
+        Box::pin(async { Ok(None) })
+    }
+}
+
+impl<F> Fetch for &mut F
+where
+    F: Fetch + ?Sized,
+{
+    fn fetch(
+        &mut self,
+        source: crate::Source,
+    ) -> Pin<Box<dyn Future<Output = Result<String, FetchError>>>> {
+        (**self).fetch(source)
+    }
+
+    fn fetch_signature(
+        &mut self,
+        source: crate::Source,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<String>, FetchError>>>> {
+        (**self).fetch_signature(source)
+    }
 }
@@ -0,0 +1,257 @@
+//! Removing unwanted endpoints from a resolved list.
+//!
+//! Where a [`SourcePolicy`](crate::policy::SourcePolicy) restricts which
+//! lists are retrieved, an [`EndpointFilter`] restricts which endpoints from
+//! those lists are used. Applying a filter removes every endpoint breaking
+//! one of its rules, and reports what was removed and why.
+//!
+//! ## Example
+//!
+//! ```
+//! use eip5139::filter::EndpointFilter;
+//! # let mut list = eip5139::RpcProviders::default();
+//!
+//! let filter = EndpointFilter::new()
+//!     .require_tls()
+//!     .deny_credentials()
+//!     .deny_host("*.tracker.example")
+//!     .deny_private_addresses();
+//!
+//! for removal in filter.apply(&mut list).removed {
+//!     // Removed endpoints may include credentials, so don't log them.
+//!     eprintln!(
+//!         "removed an endpoint for chain {} from {}: {:?}",
+//!         removal.chain_id, removal.provider, removal.reasons
+//!     );
+//! }
+//! ```
+
+use crate::policy;
+use crate::secrets::{Detector, Redacted};
+use crate::{Endpoint, Provider, RpcProviders};
+
+use std::fmt;
+
+/// Why an endpoint was removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Reason {
+    /// The endpoint isn't a valid URI, so it couldn't be checked.
+    Malformed,
+
+    /// The endpoint isn't encrypted with TLS.
+    Insecure,
+
+    /// The endpoint is addressed to a private or otherwise non-public IP
+    /// address, or to `localhost`.
+    PrivateAddress,
+
+    /// The endpoint's host matches a denied pattern.
+    #[non_exhaustive]
+    DeniedHost {
+        /// The pattern the host matched.
+        pattern: String,
+    },
+
+    /// The endpoint appears to include credentials, like an API key.
+    Credentials,
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed => write!(f, "not a valid URI"),
+            Self::Insecure => write!(f, "not encrypted with TLS"),
+            Self::PrivateAddress => write!(f, "on a private network"),
+            Self::DeniedHost { pattern } => write!(f, "host matches `{}`", pattern),
+            Self::Credentials => write!(f, "includes credentials"),
+        }
+    }
+}
+
+/// An endpoint removed by an [`EndpointFilter`].
+#[derive(Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Removal {
+    /// Key of the provider that listed the endpoint.
+    pub provider: String,
+
+    /// The chain the endpoint was listed for.
+    pub chain_id: u64,
+
+    /// The endpoint, as it appeared in the list. Redacted in the `Debug`
+    /// output.
+    pub endpoint: String,
+
+    /// Every rule the endpoint broke.
+    pub reasons: Vec<Reason>,
+}
+
+impl fmt::Debug for Removal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Removal")
+            .field("provider", &self.provider)
+            .field("chain_id", &self.chain_id)
+            .field("endpoint", &Redacted(&self.endpoint))
+            .field("reasons", &self.reasons)
+            .finish()
+    }
+}
+
+/// Outcome of filtering a list.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FilterReport {
+    /// The removed endpoints, in the order they were listed.
+    pub removed: Vec<Removal>,
+}
+
+/// Removes endpoints that break any of a set of rules.
+///
+/// A new filter has no rules, and only removes endpoints that aren't valid
+/// URIs.
+#[derive(Debug, Clone, Default)]
+pub struct EndpointFilter {
+    tls: bool,
+    credentials: Option<Detector>,
+    private: bool,
+    denied_hosts: Vec<String>,
+}
+
+impl EndpointFilter {
+    /// Create a filter with no rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remove endpoints that aren't encrypted with TLS (ie. anything but
+    /// `https://` and `wss://`.)
+    pub fn require_tls(mut self) -> Self {
+        self.tls = true;
+        self
+    }
+
+    /// Remove endpoints that appear to include credentials, as found by the
+    /// default [`Detector`]: a user name or password, an API key in a known
+    /// provider's endpoint or a path segment that looks like one, or a query
+    /// parameter like `apikey` or `token`.
+    pub fn deny_credentials(self) -> Self {
+        self.deny_secrets(Detector::new())
+    }
+
+    /// Remove endpoints where `detector` finds any secrets.
+    pub fn deny_secrets(mut self, detector: Detector) -> Self {
+        self.credentials = Some(detector);
+        self
+    }
+
+    /// Remove endpoints addressed to private, loopback, link local and other
+    /// non-public IP addresses, or to `localhost`.
+    pub fn deny_private_addresses(mut self) -> Self {
+        self.private = true;
+        self
+    }
+
+    /// Remove endpoints whose host matches `pattern`.
+    ///
+    /// A pattern is either a host name, or `*.` followed by a domain to match
+    /// every subdomain of that domain (but not the domain itself.)
+    pub fn deny_host<S>(mut self, pattern: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.denied_hosts.push(pattern.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Every rule `endpoint` breaks. Empty if it should be kept.
+    pub fn check(&self, endpoint: &str) -> Vec<Reason> {
+        let raw = endpoint;
+        let endpoint = match Endpoint::parse(raw) {
+            Ok(e) => e,
+            Err(_) => return vec![Reason::Malformed],
+        };
+
+        let mut reasons = Vec::new();
+
+        if self.tls && !endpoint.is_secure() {
+            reasons.push(Reason::Insecure);
+        }
+
+        let host = policy::host(&endpoint);
+
+        if self.private {
+            let private = match endpoint.ip() {
+                Some(ip) => policy::is_private(ip),
+                None => policy::is_local(&host),
+            };
+
+            if private {
+                reasons.push(Reason::PrivateAddress);
+            }
+        }
+
+        if let Some(pattern) = self.denied_hosts.iter().find(|p| policy::matches(p, &host)) {
+            reasons.push(Reason::DeniedHost {
+                pattern: pattern.clone(),
+            });
+        }
+
+        if let Some(ref detector) = self.credentials {
+            if detector.contains(raw) {
+                reasons.push(Reason::Credentials);
+            }
+        }
+
+        reasons
+    }
+
+    /// Remove every endpoint breaking a rule from `list`, along with any
+    /// chains left without endpoints.
+    pub fn apply(&self, list: &mut RpcProviders) -> FilterReport {
+        let mut providers = list.providers().to_vec();
+        let report = self.run(&mut providers);
+        list.set_providers(providers);
+        report
+    }
+
+    /// Like [`apply`](Self::apply), but leaves `list` unchanged and returns
+    /// the filtered copy.
+    pub fn filter(&self, list: &RpcProviders) -> (RpcProviders, FilterReport) {
+        let mut providers = list.providers().to_vec();
+        let report = self.run(&mut providers);
+
+        let mut filtered = list.clone_metadata();
+        filtered.set_providers(providers);
+        (filtered, report)
+    }
+
+    fn run(&self, providers: &mut [Provider]) -> FilterReport {
+        let mut report = FilterReport::default();
+
+        for provider in providers {
+            for chain in &mut provider.chains {
+                let chain_id = chain.chain_id;
+
+                chain.endpoints.retain(|endpoint| {
+                    let reasons = self.check(endpoint);
+                    if reasons.is_empty() {
+                        return true;
+                    }
+
+                    report.removed.push(Removal {
+                        provider: provider.key.clone(),
+                        chain_id,
+                        endpoint: endpoint.clone(),
+                        reasons,
+                    });
+                    false
+                });
+            }
+
+            provider.chains.retain(|c| !c.endpoints.is_empty());
+        }
+
+        report
+    }
+}
@@ -0,0 +1,235 @@
+//! Probing the endpoints of a resolved list.
+//!
+//! ## Example
+//!
+//! ```
+//! use eip5139::health::HealthCheck;
+//! # use eip5139::RpcProviders;
+//! # use eip5139::errors::TransportError;
+//! # use eip5139::transport::Transport;
+//! # use serde_json::Value;
+//! # use std::future::Future;
+//! # use std::pin::Pin;
+//! # struct NoTransport;
+//! # impl Transport for NoTransport {
+//! #     fn send(&mut self, _: &str, _: Value) ->
+//! #         Pin<Box<dyn Future<Output = Result<Value, TransportError>>>>
+//! #     {
+//! #         unimplemented!()
+//! #     }
+//! # }
+//! # let transport = NoTransport;
+//! # let list = RpcProviders::default();
+//!
+//! let future = async {
+//!     let report = HealthCheck::new(transport).max_lag(5).check(&list).await;
+//!
+//!     for unhealthy in report.unhealthy() {
+//!         eprintln!("{}: {:?}", unhealthy.endpoint, unhealthy.status);
+//!     }
+//! };
+//! ```
+
+use crate::errors::RpcError;
+use crate::secrets::Redacted;
+use crate::timer::Timer;
+use crate::transport::{self, Transport};
+use crate::RpcProviders;
+
+use serde_json::json;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+/// Outcome of probing a single endpoint.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Status {
+    /// The endpoint serves the declared chain, and is not lagging.
+    Healthy,
+
+    /// The endpoint serves a different chain than declared.
+    #[non_exhaustive]
+    WrongChain {
+        /// The chain id reported by the endpoint.
+        actual: u64,
+    },
+
+    /// The endpoint is too many blocks behind the other endpoints for its
+    /// chain.
+    #[non_exhaustive]
+    Lagging {
+        /// Number of blocks behind the most recent block seen.
+        behind: u64,
+    },
+
+    /// The endpoint could not be queried.
+    #[non_exhaustive]
+    Unreachable {
+        /// The underlying cause.
+        source: RpcError,
+    },
+}
+
+impl Status {
+    /// Whether this status is [`Status::Healthy`].
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, Self::Healthy)
+    }
+}
+
+/// Result of probing one endpoint of one provider.
+#[non_exhaustive]
+pub struct EndpointHealth {
+    /// Key of the provider listing this endpoint.
+    pub provider: String,
+
+    /// The chain id declared for this endpoint.
+    pub chain_id: u64,
+
+    /// The address of the endpoint.
+    pub endpoint: String,
+
+    /// Overall outcome of the probe.
+    pub status: Status,
+
+    /// Round trip time of the `eth_chainId` request, if it succeeded.
+    pub latency: Option<Duration>,
+
+    /// Block number reported by `eth_blockNumber`, if it succeeded.
+    pub block_number: Option<u64>,
+}
+
+impl fmt::Debug for EndpointHealth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EndpointHealth")
+            .field("provider", &self.provider)
+            .field("chain_id", &self.chain_id)
+            .field("endpoint", &Redacted(&self.endpoint))
+            .field("status", &self.status)
+            .field("latency", &self.latency)
+            .field("block_number", &self.block_number)
+            .finish()
+    }
+}
+
+/// Health of every endpoint in a list.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct HealthReport {
+    /// Results for each endpoint, in the order they were probed.
+    pub endpoints: Vec<EndpointHealth>,
+}
+
+impl HealthReport {
+    /// Get the endpoints that are healthy.
+    pub fn healthy(&self) -> impl Iterator<Item = &EndpointHealth> {
+        self.endpoints.iter().filter(|e| e.status.is_healthy())
+    }
+
+    /// Get the endpoints that aren't healthy.
+    pub fn unhealthy(&self) -> impl Iterator<Item = &EndpointHealth> {
+        self.endpoints.iter().filter(|e| !e.status.is_healthy())
+    }
+}
+
+/// Probes endpoints with `eth_chainId` and `eth_blockNumber`.
+#[derive(Debug)]
+pub struct HealthCheck<T> {
+    transport: T,
+    max_lag: u64,
+}
+
+impl<T> HealthCheck<T>
+where
+    T: Transport,
+{
+    /// Create a health check sending requests through `transport`.
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            max_lag: 10,
+        }
+    }
+
+    /// Consider endpoints more than `blocks` behind the most recent block
+    /// seen on their chain to be lagging. Defaults to 10.
+    pub fn max_lag(mut self, blocks: u64) -> Self {
+        self.max_lag = blocks;
+        self
+    }
+
+    async fn probe(
+        &mut self,
+        chain_id: u64,
+        endpoint: &str,
+    ) -> (Status, Option<Duration>, Option<u64>) {
+        let timer = Timer::start();
+        let reported = transport::call(&mut self.transport, endpoint, "eth_chainId", json!([]))
+            .await
+            .and_then(|v| transport::parse_quantity(&v));
+        let latency = timer.elapsed();
+
+        let actual = match reported {
+            Ok(actual) => actual,
+            Err(source) => return (Status::Unreachable { source }, None, None),
+        };
+
+        if actual != chain_id {
+            return (Status::WrongChain { actual }, Some(latency), None);
+        }
+
+        let block = transport::call(&mut self.transport, endpoint, "eth_blockNumber", json!([]))
+            .await
+            .and_then(|v| transport::parse_quantity(&v));
+
+        match block {
+            Ok(block) => (Status::Healthy, Some(latency), Some(block)),
+            Err(source) => (Status::Unreachable { source }, Some(latency), None),
+        }
+    }
+
+    /// Probe every endpoint in `providers`, one at a time.
+    pub async fn check(&mut self, providers: &RpcProviders) -> HealthReport {
+        let mut report = HealthReport::default();
+
+        for provider in providers.providers() {
+            for chain in &provider.chains {
+                for endpoint in &chain.endpoints {
+                    let (status, latency, block_number) =
+                        self.probe(chain.chain_id, endpoint).await;
+
+                    report.endpoints.push(EndpointHealth {
+                        provider: provider.key.clone(),
+                        chain_id: chain.chain_id,
+                        endpoint: endpoint.clone(),
+                        status,
+                        latency,
+                        block_number,
+                    });
+                }
+            }
+        }
+
+        // Compare each endpoint against the most recent block on its chain.
+        let mut highest = HashMap::<u64, u64>::new();
+        for health in &report.endpoints {
+            if let Some(block) = health.block_number {
+                let entry = highest.entry(health.chain_id).or_insert(block);
+                *entry = (*entry).max(block);
+            }
+        }
+
+        for health in &mut report.endpoints {
+            if let (Status::Healthy, Some(block)) = (&health.status, health.block_number) {
+                let behind = highest[&health.chain_id] - block;
+                if behind > self.max_lag {
+                    health.status = Status::Lagging { behind };
+                }
+            }
+        }
+
+        report
+    }
+}
@@ -10,24 +10,67 @@
 #![warn(missing_docs)]
 #![warn(missing_debug_implementations)]
 
+pub mod add_chain;
+pub mod aggregate;
+pub mod canonical;
+pub mod chains;
+pub mod diff;
+pub mod endpoint;
 pub mod errors;
+pub mod events;
+pub mod failover;
 mod fetch;
+pub mod filter;
+pub mod health;
+pub mod lint;
+pub mod normalize;
+pub mod overrides;
+pub mod policy;
+pub mod query;
 mod resolve;
+pub mod secrets;
+pub mod select;
+pub mod signature;
+pub mod subscription;
+mod timer;
+pub mod transport;
+pub mod verify;
 #[cfg(target_family = "wasm")]
 mod wasm;
 
+pub use self::endpoint::Endpoint;
 pub use self::errors::Error;
 pub use self::fetch::Fetch;
+pub use self::resolve::Resolver;
+
+use self::canonical::{Algorithm, ContentHash};
+use self::endpoint::Transport;
+use self::errors::{EndpointError, TimestampError};
+use self::query::{ChainEndpoint, ChainIndex};
+
+use once_cell::sync::OnceCell;
 
 use semver::{BuildMetadata, Prerelease};
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
 
 #[cfg(target_family = "wasm")]
 use wasm_bindgen::prelude::*;
 
 /// Endpoints supported for a particular chain.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// The `Debug` output redacts secrets in endpoints (see [`secrets`].)
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct ProviderChain {
     /// The identifier for a particular chain (eg. `1` for Ethereum mainnet.)
@@ -38,10 +81,64 @@ pub struct ProviderChain {
     pub endpoints: Vec<String>,
 }
 
+impl ProviderChain {
+    /// Create an entry for the chain identified by `chain_id`, served by
+    /// `endpoints`.
+    pub fn new<I, E>(chain_id: u64, endpoints: I) -> Self
+    where
+        I: IntoIterator<Item = E>,
+        E: Into<String>,
+    {
+        Self {
+            chain_id,
+            endpoints: endpoints.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Parse each of this chain's [`endpoints`](Self::endpoints).
+    pub fn parsed_endpoints(&self) -> impl Iterator<Item = Result<Endpoint, EndpointError>> + '_ {
+        self.endpoints.iter().map(|e| Endpoint::parse(e))
+    }
+
+    /// Get the valid endpoints reached using `transport`.
+    pub fn endpoints_with(&self, transport: Transport) -> impl Iterator<Item = Endpoint> + '_ {
+        self.parsed_endpoints()
+            .filter_map(Result::ok)
+            .filter(move |e| e.transport() == Some(transport))
+    }
+
+    /// Get the valid endpoints reached over HTTP or HTTPS.
+    pub fn http_endpoints(&self) -> impl Iterator<Item = Endpoint> + '_ {
+        self.endpoints_with(Transport::Http)
+    }
+
+    /// Get the valid endpoints reached over WebSocket.
+    pub fn websocket_endpoints(&self) -> impl Iterator<Item = Endpoint> + '_ {
+        self.endpoints_with(Transport::WebSocket)
+    }
+}
+
+impl fmt::Debug for ProviderChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let endpoints: Vec<_> = self.endpoints.iter().map(|e| secrets::redact(e)).collect();
+
+        f.debug_struct("ProviderChain")
+            .field("chain_id", &self.chain_id)
+            .field("endpoints", &endpoints)
+            .finish()
+    }
+}
+
 /// A single entity that serves the Ethereum JSON RPC interface for one or more chains.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct Provider {
+    /// The key identifying this provider in the list it came from.
+    ///
+    /// Empty for providers that weren't retrieved from a list.
+    #[serde(skip)]
+    pub key: String,
+
     /// Human-readable name of the provider.
     pub name: String,
 
@@ -57,10 +154,32 @@ pub struct Provider {
     pub chains: Vec<ProviderChain>,
 }
 
+impl Provider {
+    /// Create a provider with no chains, identified by `key`.
+    pub fn new<K, N>(key: K, name: N) -> Self
+    where
+        K: Into<String>,
+        N: Into<String>,
+    {
+        Self {
+            key: key.into(),
+            name: name.into(),
+            logo: None,
+            priority: None,
+            chains: Vec::new(),
+        }
+    }
+
+    /// Get this provider's entry for the chain identified by `chain_id`.
+    pub fn chain(&self, chain_id: u64) -> Option<&ProviderChain> {
+        self.chains.iter().find(|c| c.chain_id == chain_id)
+    }
+}
+
 /// [Semantic version] of an [`RpcProviders`] list.
 ///
 /// [Semantic version]: https://semver.org/
-#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
 #[non_exhaustive]
 pub struct Version {
     /// The major version indicates backwards compatibility.
@@ -103,6 +222,303 @@ impl Version {
     }
 }
 
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+
+        if let Some(ref pre_release) = self.pre_release {
+            write!(f, "-{}", pre_release)?;
+        }
+
+        if let Some(ref build) = self.build {
+            write!(f, "+{}", build)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The creation time of an [`RpcProviders`] list, parsed from RFC 3339.
+///
+/// Timestamps compare by the instant they represent, so
+/// `2024-08-08T00:00:00Z` and `2024-08-08T02:00:00+02:00` are equal. The
+/// original text is kept, and is what [`Display`](fmt::Display) and
+/// serialization produce.
+#[derive(Debug, Clone)]
+pub struct Timestamp {
+    text: String,
+    at: OffsetDateTime,
+}
+
+impl Timestamp {
+    /// Parse an RFC 3339 timestamp (eg. `2024-08-08T00:00:00Z`.)
+    pub fn parse(text: &str) -> Result<Self, TimestampError> {
+        let at = OffsetDateTime::parse(text, &Rfc3339).map_err(TimestampError)?;
+        Ok(Self {
+            text: text.to_owned(),
+            at,
+        })
+    }
+
+    /// The timestamp as originally written.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Seconds since the Unix epoch.
+    pub fn unix_timestamp(&self) -> i64 {
+        self.at.unix_timestamp()
+    }
+
+    /// The timestamp as a [`SystemTime`].
+    pub fn to_system_time(&self) -> SystemTime {
+        self.at.into()
+    }
+}
+
+impl Default for Timestamp {
+    /// The Unix epoch.
+    fn default() -> Self {
+        Self {
+            text: "1970-01-01T00:00:00Z".to_owned(),
+            at: OffsetDateTime::UNIX_EPOCH,
+        }
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = TimestampError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl PartialEq for Timestamp {
+    fn eq(&self, other: &Self) -> bool {
+        self.at == other.at
+    }
+}
+
+impl Eq for Timestamp {}
+
+impl PartialOrd for Timestamp {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Timestamp {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.at.cmp(&other.at)
+    }
+}
+
+impl Hash for Timestamp {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.at.hash(state)
+    }
+}
+
+impl PartialEq<str> for Timestamp {
+    fn eq(&self, other: &str) -> bool {
+        Self::parse(other).map(|o| *self == o).unwrap_or(false)
+    }
+}
+
+impl PartialEq<&str> for Timestamp {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.text)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let text = String::deserialize(deserializer)?;
+        Self::parse(&text).map_err(serde::de::Error::custom)
+    }
+}
+
+/// How a [`VersionRange`] matches the version of a parent list.
+///
+/// Only [`Exact`](Self::Exact) and [`Caret`](Self::Caret) are part of the
+/// EIP-5139 schema. The remaining modes are only accepted when resolving with
+/// [`RangeGrammar::Extended`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "mode")]
+#[non_exhaustive]
+pub enum RangeMode {
+    /// Only the exact version (and pre-release) matches.
+    #[serde(rename = "=")]
+    #[non_exhaustive]
+    Exact {
+        /// The pre-release string the parent version must have.
+        #[serde(
+            default,
+            rename = "preRelease",
+            skip_serializing_if = "Option::is_none"
+        )]
+        pre_release: Option<String>,
+    },
+
+    /// Any backwards compatible version matches.
+    #[serde(rename = "^")]
+    Caret,
+
+    /// Any version with the same major and minor version matches.
+    #[serde(rename = "~")]
+    Tilde,
+
+    /// Any greater version matches.
+    #[serde(rename = ">")]
+    Greater,
+
+    /// The same or any greater version matches.
+    #[serde(rename = ">=")]
+    GreaterEq,
+
+    /// Any lesser version matches.
+    #[serde(rename = "<")]
+    Less,
+
+    /// The same or any lesser version matches.
+    #[serde(rename = "<=")]
+    LessEq,
+}
+
+impl Default for RangeMode {
+    fn default() -> Self {
+        Self::Caret
+    }
+}
+
+impl RangeMode {
+    fn symbol(&self) -> &'static str {
+        match self {
+            Self::Exact { .. } => "=",
+            Self::Caret => "^",
+            Self::Tilde => "~",
+            Self::Greater => ">",
+            Self::GreaterEq => ">=",
+            Self::Less => "<",
+            Self::LessEq => "<=",
+        }
+    }
+}
+
+/// Which version range syntax extension lists may use in `extends.version`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RangeGrammar {
+    /// Only the syntax in the EIP-5139 schema: a single range with the `^` or
+    /// `=` mode.
+    Strict,
+
+    /// Also accept the `~`, `>`, `>=`, `<` and `<=` modes, and an array of
+    /// ranges which must all match (eg. `>=2.3.0` and `<3.0.0`.)
+    Extended,
+}
+
+impl Default for RangeGrammar {
+    fn default() -> Self {
+        Self::Strict
+    }
+}
+
+/// Range of parent list versions that an extension list is compatible with.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct VersionRange {
+    /// The major version of the range.
+    pub major: u64,
+
+    /// The minor version of the range.
+    pub minor: u64,
+
+    /// The patch version of the range.
+    pub patch: u64,
+
+    /// How versions are matched against the range. Defaults to
+    /// [`RangeMode::Caret`] when absent.
+    #[serde(flatten, default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<RangeMode>,
+}
+
+impl VersionRange {
+    fn into_comparator(self) -> semver::Comparator {
+        let mode = self.mode.unwrap_or_default();
+        let mut pre = None;
+
+        let op = match mode {
+            RangeMode::Caret => semver::Op::Caret,
+            RangeMode::Exact { pre_release } => {
+                pre = pre_release.map(|p| Prerelease::new(&p).unwrap());
+                semver::Op::Exact
+            }
+            RangeMode::Tilde => semver::Op::Tilde,
+            RangeMode::Greater => semver::Op::Greater,
+            RangeMode::GreaterEq => semver::Op::GreaterEq,
+            RangeMode::Less => semver::Op::Less,
+            RangeMode::LessEq => semver::Op::LessEq,
+        };
+
+        semver::Comparator {
+            op,
+            pre: pre.unwrap_or(Prerelease::EMPTY),
+            major: self.major,
+            minor: Some(self.minor),
+            patch: Some(self.patch),
+        }
+    }
+
+    fn into_semver(self) -> semver::VersionReq {
+        semver::VersionReq {
+            comparators: vec![self.into_comparator()],
+        }
+    }
+}
+
+impl fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mode = self.mode.clone().unwrap_or_default();
+        write!(
+            f,
+            "{}{}.{}.{}",
+            mode.symbol(),
+            self.major,
+            self.minor,
+            self.patch
+        )?;
+
+        if let RangeMode::Exact {
+            pre_release: Some(pre_release),
+        } = mode
+        {
+            write!(f, "-{}", pre_release)?;
+        }
+
+        Ok(())
+    }
+}
+
 /// The location where a list can be retrieved from.
 #[derive(Debug, Serialize, Deserialize, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub enum Source {
@@ -117,6 +533,67 @@ pub enum Source {
     Uri(String),
 }
 
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ens(name) => write!(f, "ens:{}", name),
+            Self::Uri(uri) => write!(f, "{}", uri),
+        }
+    }
+}
+
+/// Unique, non-empty keys for `providers`, whose serialized forms are
+/// `values`.
+///
+/// Providers with a unique key keep it. The rest are keyed by their key (or
+/// `provider`, if empty) followed by a digest of their contents, so the keys
+/// don't depend on the order of providers.
+fn provider_keys(providers: &[Provider], values: &[serde_json::Value]) -> Vec<String> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for provider in providers {
+        *counts.entry(&provider.key).or_default() += 1;
+    }
+
+    let unique = |key: &str| !key.is_empty() && counts[key] == 1;
+
+    let mut keys: Vec<_> = providers
+        .iter()
+        .map(|p| unique(&p.key).then(|| p.key.clone()))
+        .collect();
+    let mut used: BTreeSet<_> = keys.iter().flatten().cloned().collect();
+
+    let mut derived: Vec<_> = providers
+        .iter()
+        .zip(values)
+        .enumerate()
+        .filter(|(_, (p, _))| !unique(&p.key))
+        .map(|(idx, (p, value))| {
+            let base = if p.key.is_empty() { "provider" } else { &p.key };
+            let digest = canonical::hash(value, Algorithm::Keccak256);
+            (
+                format!("{}-{}", base, hex::encode(&digest.as_bytes()[..4])),
+                idx,
+            )
+        })
+        .collect();
+    derived.sort();
+
+    // Only identical providers share a derived key, so it doesn't matter
+    // which of them gets which suffix.
+    for (candidate, idx) in derived {
+        let mut key = candidate.clone();
+        let mut suffix = 1;
+        while used.contains(&key) {
+            suffix += 1;
+            key = format!("{}-{}", candidate, suffix);
+        }
+        used.insert(key.clone());
+        keys[idx] = Some(key);
+    }
+
+    keys.into_iter().map(Option::unwrap).collect()
+}
+
 /// A resolved EIP-5139 provider list.
 ///
 /// Retrieve a list using [`fetch`](RpcProviders::fetch).
@@ -129,20 +606,24 @@ pub struct RpcProviders {
     /// An optional URI where a logo for this list can be found.
     pub logo: Option<String>,
 
-    /// The date/time this list was created (in RFC 3339 format.)
-    pub timestamp: String,
+    /// The date/time this list was created.
+    #[cfg_attr(target_family = "wasm", wasm_bindgen(skip))]
+    pub timestamp: Timestamp,
 
     version: Version,
     providers: Vec<Provider>,
+    index: OnceCell<ChainIndex>,
 }
 
 impl RpcProviders {
     /// Retrieve the list from `source` and resolve any extension lists.
-    pub async fn fetch<F>(mut fetch: F, source: Source) -> Result<Self, Error>
+    ///
+    /// Use a [`Resolver`] to customize how the list is resolved.
+    pub async fn fetch<F>(fetch: F, source: Source) -> Result<Self, Error>
     where
         F: fetch::Fetch,
     {
-        resolve::resolve(&mut fetch, source).await
+        Resolver::new(fetch).resolve(source).await
     }
 
     /// Get the providers contained in this list.
@@ -152,14 +633,64 @@ impl RpcProviders {
 
     /// Get a mutable reference to the providers contained in this list.
     pub fn providers_mut(&mut self) -> &mut [Provider] {
+        self.index.take();
         &mut self.providers
     }
 
     /// Set the providers contained in this list.
     pub fn set_providers(&mut self, p: Vec<Provider>) {
+        self.index.take();
         self.providers = p;
     }
 
+    /// Copy everything but the providers of this list.
+    fn clone_metadata(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            logo: self.logo.clone(),
+            timestamp: self.timestamp.clone(),
+            version: self.version.clone(),
+            ..Default::default()
+        }
+    }
+
+    fn index(&self) -> &ChainIndex {
+        self.index.get_or_init(|| ChainIndex::new(&self.providers))
+    }
+
+    /// Get the identifiers of every chain served by this list, in ascending
+    /// order.
+    pub fn chain_ids(&self) -> impl Iterator<Item = u64> + '_ {
+        self.index().chain_ids()
+    }
+
+    /// Get the providers serving the chain identified by `chain_id`, ordered
+    /// by [`priority`](Provider::priority).
+    pub fn providers_for(&self, chain_id: u64) -> impl Iterator<Item = &Provider> + '_ {
+        self.index()
+            .chains(&self.providers, chain_id)
+            .map(|(provider, _)| provider)
+    }
+
+    /// Get every endpoint serving the chain identified by `chain_id`, ordered
+    /// by provider [`priority`](Provider::priority).
+    ///
+    /// Endpoints from the same provider keep the order they were listed in.
+    ///
+    /// The lookup table behind this method is built on first use, and
+    /// rebuilt only after the providers are modified.
+    pub fn endpoints_for(&self, chain_id: u64) -> impl Iterator<Item = ChainEndpoint<'_>> + '_ {
+        self.index()
+            .chains(&self.providers, chain_id)
+            .flat_map(|(provider, chain)| {
+                chain.endpoints.iter().map(move |endpoint| ChainEndpoint {
+                    provider,
+                    chain,
+                    endpoint,
+                })
+            })
+    }
+
     /// Get the version of this list.
     pub fn version(&self) -> &Version {
         &self.version
@@ -174,4 +705,68 @@ impl RpcProviders {
     pub fn set_version(&mut self, version: Version) {
         self.version = version;
     }
+
+    /// This list as a root list document, with every extension list applied.
+    ///
+    /// Providers without a key, or sharing a key with another provider (eg.
+    /// after [`set_providers`](Self::set_providers)), are given a key derived
+    /// from their contents, so none of them are lost.
+    pub fn to_json(&self) -> serde_json::Value {
+        let values: Vec<_> = self
+            .providers
+            .iter()
+            .map(|p| serde_json::to_value(p).unwrap())
+            .collect();
+
+        let providers: serde_json::Map<_, _> = provider_keys(&self.providers, &values)
+            .into_iter()
+            .zip(values)
+            .collect();
+
+        let mut json = serde_json::json!({
+            "name": self.name,
+            "version": self.version,
+            "timestamp": self.timestamp,
+            "providers": providers,
+        });
+
+        if let Some(ref logo) = self.logo {
+            json["logo"] = logo.as_str().into();
+        }
+
+        json
+    }
+
+    /// Hash the [canonical](canonical) form of [`to_json`](Self::to_json).
+    ///
+    /// The hash doesn't depend on the order or formatting of the documents
+    /// the list was resolved from, nor on the order of providers.
+    pub fn content_hash(&self, algorithm: Algorithm) -> ContentHash {
+        canonical::hash(&self.to_json(), algorithm)
+    }
+
+    /// Check that `update`, a later retrieval of this same list, isn't a
+    /// newer version with an older timestamp.
+    ///
+    /// Versions are compared by semantic versioning precedence, so build
+    /// metadata is ignored.
+    pub fn check_update(&self, update: &RpcProviders) -> Result<(), Error> {
+        let precedence = |v: &Version| semver::Version {
+            build: BuildMetadata::EMPTY,
+            ..v.clone().into_semver()
+        };
+
+        if precedence(&update.version) > precedence(&self.version)
+            && update.timestamp < self.timestamp
+        {
+            return Err(Error::TimestampRegression {
+                previous: Box::new(self.version.clone()),
+                previous_timestamp: Box::new(self.timestamp.clone()),
+                version: Box::new(update.version.clone()),
+                timestamp: Box::new(update.timestamp.clone()),
+            });
+        }
+
+        Ok(())
+    }
 }
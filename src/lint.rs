@@ -0,0 +1,627 @@
+//! Checks for problems the JSON Schema can't express.
+//!
+//! A [`Linter`] runs a set of [`Rule`]s against either a raw list document
+//! (optionally alongside its parent) or a resolved [`RpcProviders`], and
+//! returns a [`Diagnostic`] for each problem found. Every rule has an
+//! identifier and a default [`Severity`], which can be changed or disabled
+//! per linter.
+//!
+//! ## Example
+//!
+//! ```
+//! use eip5139::lint::{Linter, Severity};
+//! # let list = eip5139::RpcProviders::default();
+//!
+//! let linter = Linter::new()
+//!     .severity("insecure-endpoint", Severity::Error)
+//!     .disable("priority-collision");
+//!
+//! for diagnostic in linter.lint(&list) {
+//!     eprintln!("{}", diagnostic);
+//! }
+//! ```
+
+use crate::endpoint::Scheme;
+use crate::normalize::{Normalizer, Scope};
+use crate::policy;
+use crate::secrets::Redacted;
+use crate::timer;
+use crate::{Endpoint, Provider, RpcProviders, Timestamp};
+
+use serde_json::Value;
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::time::SystemTime;
+
+/// How serious a [`Diagnostic`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Severity {
+    /// Worth knowing, but probably intentional.
+    Info,
+
+    /// Likely to cause problems for some users.
+    Warning,
+
+    /// Almost certainly a mistake.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Info => write!(f, "info"),
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A problem found by a [`Rule`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Diagnostic {
+    /// Identifier of the rule that found the problem.
+    pub rule: &'static str,
+
+    /// How serious the problem is.
+    pub severity: Severity,
+
+    /// JSON Pointer to the offending value, relative to the list document
+    /// (eg. `/providers/some-key/logo`.)
+    pub pointer: String,
+
+    /// Human-readable description of the problem.
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}[{}] {}: {}",
+            self.severity, self.rule, self.pointer, self.message
+        )
+    }
+}
+
+/// A problem reported by a rule, before its severity is decided.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Finding {
+    /// JSON Pointer to the offending value.
+    pub pointer: String,
+
+    /// Human-readable description of the problem.
+    pub message: String,
+}
+
+impl Finding {
+    /// Create a finding for the value at `pointer`.
+    pub fn new<P, M>(pointer: P, message: M) -> Self
+    where
+        P: Into<String>,
+        M: Into<String>,
+    {
+        Self {
+            pointer: pointer.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// The list being linted, as seen by a [`Rule`].
+#[derive(Debug)]
+pub struct Context<'a> {
+    logo: Option<&'a str>,
+    timestamp: &'a str,
+    parent_timestamp: Option<&'a str>,
+    providers: Cow<'a, [Provider]>,
+    list: Option<&'a RpcProviders>,
+    now: SystemTime,
+}
+
+impl<'a> Context<'a> {
+    /// The logo of the list, if any.
+    pub fn logo(&self) -> Option<&'a str> {
+        self.logo
+    }
+
+    /// The timestamp of the list, as written.
+    pub fn timestamp(&self) -> &'a str {
+        self.timestamp
+    }
+
+    /// The timestamp of the parent list, if linting an extension list
+    /// document with its parent.
+    pub fn parent_timestamp(&self) -> Option<&'a str> {
+        self.parent_timestamp
+    }
+
+    /// The providers of the list.
+    ///
+    /// Empty when linting an extension list document, since its providers
+    /// aren't known until it is resolved.
+    pub fn providers(&self) -> &[Provider] {
+        &self.providers
+    }
+
+    /// The resolved list, if linting one.
+    pub fn list(&self) -> Option<&'a RpcProviders> {
+        self.list
+    }
+
+    /// The time to compare timestamps against.
+    pub fn now(&self) -> SystemTime {
+        self.now
+    }
+}
+
+/// A single check.
+pub trait Rule {
+    /// A short, stable, kebab-case identifier for this rule.
+    fn id(&self) -> &'static str;
+
+    /// The severity of this rule's diagnostics, unless overridden.
+    fn default_severity(&self) -> Severity;
+
+    /// Check `context`, returning any problems found.
+    fn check(&self, context: &Context<'_>) -> Vec<Finding>;
+}
+
+fn provider_pointer(provider: &Provider) -> String {
+    format!("/providers/{}", escape(&provider.key))
+}
+
+/// Escape a JSON Pointer reference token.
+fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Every endpoint in `providers`, with its JSON Pointer.
+fn endpoints(providers: &[Provider]) -> impl Iterator<Item = (String, &str)> {
+    providers.iter().flat_map(|p| {
+        p.chains.iter().enumerate().flat_map(move |(c, chain)| {
+            chain
+                .endpoints
+                .iter()
+                .enumerate()
+                .map(move |(e, endpoint)| {
+                    let pointer = format!("{}/chains/{}/endpoints/{}", provider_pointer(p), c, e);
+                    (pointer, endpoint.as_str())
+                })
+        })
+    })
+}
+
+/// Providers that don't serve any chains.
+#[derive(Debug, Default, Clone)]
+pub struct EmptyProvider;
+
+impl Rule for EmptyProvider {
+    fn id(&self) -> &'static str {
+        "empty-provider"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, context: &Context<'_>) -> Vec<Finding> {
+        context
+            .providers()
+            .iter()
+            .filter(|p| p.chains.is_empty())
+            .map(|p| Finding::new(provider_pointer(p), format!("`{}` has no chains", p.name)))
+            .collect()
+    }
+}
+
+/// Endpoints that aren't encrypted (`http://` and `ws://`.)
+#[derive(Debug, Default, Clone)]
+pub struct InsecureEndpoint;
+
+impl Rule for InsecureEndpoint {
+    fn id(&self) -> &'static str {
+        "insecure-endpoint"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, context: &Context<'_>) -> Vec<Finding> {
+        endpoints(context.providers())
+            .filter(|(_, e)| {
+                Endpoint::parse(e)
+                    .map(|e| matches!(e.scheme(), Scheme::Http | Scheme::Ws))
+                    .unwrap_or(false)
+            })
+            .map(|(pointer, e)| {
+                Finding::new(pointer, format!("`{}` is not encrypted", Redacted(e)))
+            })
+            .collect()
+    }
+}
+
+/// Endpoints addressed by IP, or on `localhost`.
+#[derive(Debug, Default, Clone)]
+pub struct LocalEndpoint;
+
+impl Rule for LocalEndpoint {
+    fn id(&self) -> &'static str {
+        "local-endpoint"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, context: &Context<'_>) -> Vec<Finding> {
+        let mut findings = Vec::new();
+
+        for (pointer, text) in endpoints(context.providers()) {
+            let endpoint = match Endpoint::parse(text) {
+                Ok(e) => e,
+                Err(_) => continue,
+            };
+
+            if policy::is_local(&policy::host(&endpoint)) {
+                let message = format!("`{}` is on the local machine", Redacted(text));
+                findings.push(Finding::new(pointer, message));
+            } else if endpoint.ip().is_some() {
+                let message = format!("`{}` uses an IP address instead of a name", Redacted(text));
+                findings.push(Finding::new(pointer, message));
+            }
+        }
+
+        findings
+    }
+}
+
+/// Different providers with the same name.
+#[derive(Debug, Default, Clone)]
+pub struct DuplicateProviderName;
+
+impl Rule for DuplicateProviderName {
+    fn id(&self) -> &'static str {
+        "duplicate-provider-name"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, context: &Context<'_>) -> Vec<Finding> {
+        let mut first = HashMap::<String, &Provider>::new();
+        let mut findings = Vec::new();
+
+        for provider in context.providers() {
+            let name = provider.name.to_lowercase();
+            match first.get(&name) {
+                Some(other) => findings.push(Finding::new(
+                    format!("{}/name", provider_pointer(provider)),
+                    format!("`{}` has the same name as `{}`", provider.key, other.key),
+                )),
+                None => {
+                    first.insert(name, provider);
+                }
+            }
+        }
+
+        findings
+    }
+}
+
+/// Providers sharing a priority while serving the same chain, which leaves
+/// their order up to the wallet.
+#[derive(Debug, Default, Clone)]
+pub struct PriorityCollision;
+
+impl Rule for PriorityCollision {
+    fn id(&self) -> &'static str {
+        "priority-collision"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn check(&self, context: &Context<'_>) -> Vec<Finding> {
+        let mut seen = BTreeMap::<(u64, u32), &Provider>::new();
+        let mut findings = Vec::new();
+
+        for provider in context.providers() {
+            let priority = match provider.priority {
+                Some(p) => p,
+                None => continue,
+            };
+
+            for chain in &provider.chains {
+                match seen.get(&(chain.chain_id, priority)) {
+                    Some(other) if other.key != provider.key => findings.push(Finding::new(
+                        format!("{}/priority", provider_pointer(provider)),
+                        format!(
+                            "`{}` and `{}` both have priority {} on chain {}",
+                            other.key, provider.key, priority, chain.chain_id
+                        ),
+                    )),
+                    Some(_) => (),
+                    None => {
+                        seen.insert((chain.chain_id, priority), provider);
+                    }
+                }
+            }
+        }
+
+        findings
+    }
+}
+
+/// Timestamps in the future, or earlier than the parent list's.
+#[derive(Debug, Default, Clone)]
+pub struct TimestampOrder;
+
+impl Rule for TimestampOrder {
+    fn id(&self) -> &'static str {
+        "timestamp-order"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, context: &Context<'_>) -> Vec<Finding> {
+        let timestamp = match Timestamp::parse(context.timestamp()) {
+            Ok(t) => t,
+            Err(_) => {
+                let message = format!("`{}` is not an RFC 3339 timestamp", context.timestamp());
+                return vec![Finding::new("/timestamp", message)];
+            }
+        };
+
+        let mut findings = Vec::new();
+
+        if timestamp.to_system_time() > context.now() {
+            let message = format!("`{}` is in the future", context.timestamp());
+            findings.push(Finding::new("/timestamp", message));
+        }
+
+        let parent = context
+            .parent_timestamp()
+            .and_then(|p| Timestamp::parse(p).ok());
+        if let Some(parent) = parent {
+            if timestamp < parent {
+                let message = format!(
+                    "`{}` is earlier than the parent list's `{}`",
+                    context.timestamp(),
+                    context.parent_timestamp().unwrap()
+                );
+                findings.push(Finding::new("/timestamp", message));
+            }
+        }
+
+        findings
+    }
+}
+
+/// Logos that aren't served over HTTPS.
+#[derive(Debug, Default, Clone)]
+pub struct InsecureLogo;
+
+impl Rule for InsecureLogo {
+    fn id(&self) -> &'static str {
+        "insecure-logo"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, context: &Context<'_>) -> Vec<Finding> {
+        let list = context.logo().map(|l| ("/logo".to_owned(), l));
+        let providers = context.providers().iter().filter_map(|p| {
+            p.logo
+                .as_deref()
+                .map(|l| (format!("{}/logo", provider_pointer(p)), l))
+        });
+
+        list.into_iter()
+            .chain(providers)
+            .filter(|(_, logo)| !logo.to_ascii_lowercase().starts_with("https://"))
+            .map(|(pointer, logo)| Finding::new(pointer, format!("`{}` is not https", logo)))
+            .collect()
+    }
+}
+
+/// The same endpoint listed more than once for a chain, possibly spelled
+/// differently. See [`Normalizer`].
+#[derive(Debug, Default, Clone)]
+pub struct DuplicateEndpoint;
+
+impl Rule for DuplicateEndpoint {
+    fn id(&self) -> &'static str {
+        "duplicate-endpoint"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, context: &Context<'_>) -> Vec<Finding> {
+        let mut list = RpcProviders::default();
+        list.set_providers(context.providers().to_vec());
+
+        Normalizer::new()
+            .check(&list)
+            .duplicates
+            .into_iter()
+            .map(|d| {
+                let keys: Vec<_> = d.occurrences.iter().map(|o| o.provider.as_str()).collect();
+                let scope = match d.scope {
+                    Scope::WithinProvider => "within",
+                    Scope::AcrossProviders => "across",
+                };
+
+                let message = format!(
+                    "`{}` is listed {} times for chain {} ({} providers: {})",
+                    Redacted(&d.canonical),
+                    d.occurrences.len(),
+                    d.chain_id,
+                    scope,
+                    keys.join(", ")
+                );
+
+                let provider = format!("/providers/{}", escape(&d.occurrences[0].provider));
+                Finding::new(provider, message)
+            })
+            .collect()
+    }
+}
+
+/// Runs [`Rule`]s over lists.
+pub struct Linter {
+    rules: Vec<Box<dyn Rule>>,
+    severities: HashMap<&'static str, Option<Severity>>,
+    now: Option<SystemTime>,
+}
+
+impl fmt::Debug for Linter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rules: Vec<_> = self.rules.iter().map(|r| r.id()).collect();
+        f.debug_struct("Linter")
+            .field("rules", &rules)
+            .field("severities", &self.severities)
+            .field("now", &self.now)
+            .finish()
+    }
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Linter {
+    /// Create a linter with every rule in this module.
+    pub fn new() -> Self {
+        Self::empty()
+            .rule(EmptyProvider)
+            .rule(InsecureEndpoint)
+            .rule(LocalEndpoint)
+            .rule(DuplicateProviderName)
+            .rule(PriorityCollision)
+            .rule(TimestampOrder)
+            .rule(InsecureLogo)
+            .rule(DuplicateEndpoint)
+    }
+
+    /// Create a linter with no rules.
+    pub fn empty() -> Self {
+        Self {
+            rules: Vec::new(),
+            severities: HashMap::new(),
+            now: None,
+        }
+    }
+
+    /// Add `rule` to this linter.
+    pub fn rule<R>(mut self, rule: R) -> Self
+    where
+        R: 'static + Rule,
+    {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Report diagnostics from the rule identified by `id` with `severity`.
+    pub fn severity(mut self, id: &'static str, severity: Severity) -> Self {
+        self.severities.insert(id, Some(severity));
+        self
+    }
+
+    /// Don't run the rule identified by `id`.
+    pub fn disable(mut self, id: &'static str) -> Self {
+        self.severities.insert(id, None);
+        self
+    }
+
+    /// Compare timestamps against `now` instead of the current time.
+    pub fn now(mut self, now: SystemTime) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    fn run(&self, context: &Context<'_>) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for rule in &self.rules {
+            let severity = match self.severities.get(rule.id()) {
+                Some(Some(severity)) => *severity,
+                Some(None) => continue,
+                None => rule.default_severity(),
+            };
+
+            for finding in rule.check(context) {
+                diagnostics.push(Diagnostic {
+                    rule: rule.id(),
+                    severity,
+                    pointer: finding.pointer,
+                    message: finding.message,
+                });
+            }
+        }
+
+        diagnostics
+    }
+
+    /// Lint a resolved list.
+    pub fn lint(&self, list: &RpcProviders) -> Vec<Diagnostic> {
+        let context = Context {
+            logo: list.logo.as_deref(),
+            timestamp: list.timestamp.as_str(),
+            parent_timestamp: None,
+            providers: Cow::Borrowed(list.providers()),
+            list: Some(list),
+            now: self.now.unwrap_or_else(timer::now),
+        };
+
+        self.run(&context)
+    }
+
+    /// Lint a raw list `document`, and optionally compare it to its `parent`
+    /// document.
+    ///
+    /// Documents should already be valid according to the JSON Schema; parts
+    /// of a document that don't match the schema are ignored.
+    pub fn lint_document(&self, document: &Value, parent: Option<&Value>) -> Vec<Diagnostic> {
+        let providers = document["providers"]
+            .as_object()
+            .map(|map| {
+                map.iter()
+                    .filter_map(|(key, value)| {
+                        let provider: Provider = serde_json::from_value(value.clone()).ok()?;
+                        Some(Provider {
+                            key: key.clone(),
+                            ..provider
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let context = Context {
+            logo: document["logo"].as_str(),
+            timestamp: document["timestamp"].as_str().unwrap_or_default(),
+            parent_timestamp: parent.and_then(|p| p["timestamp"].as_str()),
+            providers: Cow::Owned(providers),
+            list: None,
+            now: self.now.unwrap_or_else(timer::now),
+        };
+
+        self.run(&context)
+    }
+}
@@ -0,0 +1,240 @@
+//! Canonicalizing endpoints and finding duplicates.
+//!
+//! Lists often spell the same endpoint in different ways (eg. with a trailing
+//! slash, an uppercase host, or an explicit default port), or list the same
+//! endpoint under two providers. A [`Normalizer`] finds these duplicates using
+//! [`Endpoint::canonical`], and can either report them or collapse them.
+//!
+//! ## Example
+//!
+//! ```
+//! use eip5139::normalize::{Action, Normalizer};
+//! # let mut list = eip5139::RpcProviders::default();
+//!
+//! // As a lint:
+//! for duplicate in Normalizer::new().check(&list).duplicates {
+//!     eprintln!("{} is listed {} times", duplicate.canonical, duplicate.occurrences.len());
+//! }
+//!
+//! // As a transform:
+//! let report = Normalizer::new()
+//!     .rewrite(true)
+//!     .within(Action::Collapse)
+//!     .across(Action::Collapse)
+//!     .apply(&mut list);
+//! ```
+
+use crate::query::by_priority;
+use crate::{Endpoint, Provider, RpcProviders};
+
+use std::collections::HashMap;
+
+/// What to do with duplicate endpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Action {
+    /// Report duplicates, leaving them in place.
+    Warn,
+
+    /// Report duplicates, and keep only the first occurrence.
+    ///
+    /// Across providers, the occurrence from the highest priority provider is
+    /// kept.
+    Collapse,
+}
+
+/// Where duplicate endpoints were found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Scope {
+    /// Only one provider lists the endpoint, more than once for one chain.
+    WithinProvider,
+
+    /// More than one provider lists the endpoint for one chain.
+    AcrossProviders,
+}
+
+/// One place an endpoint is listed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Occurrence {
+    /// Key of the provider listing the endpoint.
+    pub provider: String,
+
+    /// The endpoint, as spelled by the provider.
+    pub endpoint: String,
+}
+
+/// An endpoint listed more than once for the same chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Duplicate {
+    /// The chain the endpoint is listed for.
+    pub chain_id: u64,
+
+    /// The canonical spelling of the endpoint.
+    pub canonical: String,
+
+    /// Where the duplicates were found.
+    pub scope: Scope,
+
+    /// Every occurrence of the endpoint, with the one that would be kept
+    /// when collapsing first.
+    pub occurrences: Vec<Occurrence>,
+}
+
+/// Outcome of checking or normalizing a list.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct NormalizeReport {
+    /// Endpoints whose spelling differs from their canonical form, as
+    /// `(original, canonical)` pairs.
+    pub noncanonical: Vec<(String, String)>,
+
+    /// Endpoints listed more than once.
+    pub duplicates: Vec<Duplicate>,
+}
+
+/// Finds, and optionally removes, duplicate endpoints.
+#[derive(Debug, Clone)]
+pub struct Normalizer {
+    rewrite: bool,
+    within: Action,
+    across: Action,
+}
+
+impl Default for Normalizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn canonical(endpoint: &str) -> String {
+    Endpoint::parse(endpoint)
+        .map(|e| e.canonical())
+        .unwrap_or_else(|_| endpoint.to_owned())
+}
+
+impl Normalizer {
+    /// Create a normalizer that only reports duplicates.
+    pub fn new() -> Self {
+        Self {
+            rewrite: false,
+            within: Action::Warn,
+            across: Action::Warn,
+        }
+    }
+
+    /// When applied, replace every endpoint with its canonical spelling.
+    pub fn rewrite(mut self, rewrite: bool) -> Self {
+        self.rewrite = rewrite;
+        self
+    }
+
+    /// What to do with duplicates within a single provider.
+    pub fn within(mut self, action: Action) -> Self {
+        self.within = action;
+        self
+    }
+
+    /// What to do with duplicates across providers.
+    pub fn across(mut self, action: Action) -> Self {
+        self.across = action;
+        self
+    }
+
+    /// Report non-canonical and duplicate endpoints without changing `list`.
+    pub fn check(&self, list: &RpcProviders) -> NormalizeReport {
+        let mut providers = list.providers().to_vec();
+        Self::new().run(&mut providers)
+    }
+
+    /// Report non-canonical and duplicate endpoints, and change `list`
+    /// according to this normalizer's configuration.
+    pub fn apply(&self, list: &mut RpcProviders) -> NormalizeReport {
+        let mut providers = list.providers().to_vec();
+        let report = self.run(&mut providers);
+        list.set_providers(providers);
+        report
+    }
+
+    fn run(&self, providers: &mut [Provider]) -> NormalizeReport {
+        let mut report = NormalizeReport::default();
+
+        // Visit providers in priority order, so the first occurrence of an
+        // endpoint belongs to the highest priority provider.
+        let mut order: Vec<usize> = (0..providers.len()).collect();
+        order.sort_by(|a, b| by_priority(&providers[*a], &providers[*b]));
+
+        // (chain id, canonical) -> (scope, occurrences)
+        let mut seen = HashMap::<(u64, String), (Scope, Vec<Occurrence>)>::new();
+        let mut keys = Vec::new();
+
+        for idx in order {
+            let provider = &mut providers[idx];
+
+            for chain in &mut provider.chains {
+                let mut kept = Vec::with_capacity(chain.endpoints.len());
+
+                for endpoint in chain.endpoints.drain(..) {
+                    let canonical = canonical(&endpoint);
+
+                    if canonical != endpoint {
+                        report
+                            .noncanonical
+                            .push((endpoint.clone(), canonical.clone()));
+                    }
+
+                    let key = (chain.chain_id, canonical.clone());
+                    let occurrence = Occurrence {
+                        provider: provider.key.clone(),
+                        endpoint: endpoint.clone(),
+                    };
+
+                    let collapse = match seen.get_mut(&key) {
+                        None => {
+                            keys.push(key.clone());
+                            seen.insert(key, (Scope::WithinProvider, vec![occurrence]));
+                            false
+                        }
+                        Some((scope, occurrences)) => {
+                            let within = occurrences.iter().any(|o| o.provider == provider.key);
+
+                            let action = if within {
+                                self.within
+                            } else {
+                                *scope = Scope::AcrossProviders;
+                                self.across
+                            };
+
+                            occurrences.push(occurrence);
+                            action == Action::Collapse
+                        }
+                    };
+
+                    if !collapse {
+                        kept.push(if self.rewrite { canonical } else { endpoint });
+                    }
+                }
+
+                chain.endpoints = kept;
+            }
+
+            provider.chains.retain(|c| !c.endpoints.is_empty());
+        }
+
+        for key in keys {
+            let (scope, occurrences) = seen.remove(&key).unwrap();
+            if occurrences.len() > 1 {
+                report.duplicates.push(Duplicate {
+                    chain_id: key.0,
+                    canonical: key.1,
+                    scope,
+                    occurrences,
+                });
+            }
+        }
+
+        report
+    }
+}
@@ -0,0 +1,261 @@
+//! Local changes layered on top of a resolved list.
+//!
+//! [`Overrides`] let a user add or hide providers, add or remove endpoints,
+//! and change priorities without publishing an extension list. Overrides
+//! refer to providers by [`key`](crate::Provider::key), so they keep applying
+//! when the upstream list is updated. Overrides that no longer match anything
+//! upstream are reported when applied.
+//!
+//! ## Example
+//!
+//! ```
+//! use eip5139::overrides::Overrides;
+//! use eip5139::{Provider, ProviderChain};
+//! # let mut list = eip5139::RpcProviders::default();
+//!
+//! let mut private = Provider::new("my-node", "My Node");
+//! private.chains.push(ProviderChain::new(1, ["http://localhost:8545"]));
+//!
+//! let mut overrides = Overrides::default();
+//! overrides.add_provider(private);
+//! overrides.remove_provider("some-provider");
+//! overrides.set_priority("my-node", 0);
+//!
+//! // Persist the overrides somewhere.
+//! let json = serde_json::to_string(&overrides).unwrap();
+//!
+//! for stale in overrides.apply(&mut list) {
+//!     eprintln!("override no longer applies: {:?}", stale);
+//! }
+//! ```
+
+use crate::query::by_priority;
+use crate::{Provider, ProviderChain, RpcProviders};
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// An endpoint of a particular provider and chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct EndpointOverride {
+    /// Key of the provider.
+    pub provider: String,
+
+    /// The chain the endpoint serves.
+    #[serde(rename = "chainId")]
+    pub chain_id: u64,
+
+    /// The address of the endpoint.
+    pub endpoint: String,
+}
+
+/// An override that had no effect on the list it was applied to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StaleOverride {
+    /// A provider to remove isn't in the list.
+    #[non_exhaustive]
+    RemovedProviderMissing {
+        /// Key of the provider.
+        provider: String,
+    },
+
+    /// A provider to receive an endpoint isn't in the list.
+    AddedEndpointProviderMissing(EndpointOverride),
+
+    /// An endpoint to add is already listed.
+    AddedEndpointPresent(EndpointOverride),
+
+    /// An endpoint to remove isn't listed.
+    RemovedEndpointMissing(EndpointOverride),
+
+    /// A provider to change the priority of isn't in the list.
+    #[non_exhaustive]
+    PriorityProviderMissing {
+        /// Key of the provider.
+        provider: String,
+    },
+}
+
+/// A set of local changes to apply to an [`RpcProviders`].
+///
+/// Changes are applied in this order: providers are removed, providers are
+/// added (replacing any upstream provider with the same key), endpoints are
+/// removed, endpoints are added, and finally priorities are changed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Overrides {
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    remove_providers: BTreeSet<String>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    add_providers: BTreeMap<String, Provider>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    remove_endpoints: Vec<EndpointOverride>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    add_endpoints: Vec<EndpointOverride>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    priorities: BTreeMap<String, u32>,
+}
+
+impl Overrides {
+    /// Whether these overrides make no changes.
+    pub fn is_empty(&self) -> bool {
+        self.remove_providers.is_empty()
+            && self.add_providers.is_empty()
+            && self.remove_endpoints.is_empty()
+            && self.add_endpoints.is_empty()
+            && self.priorities.is_empty()
+    }
+
+    /// Add `provider`, replacing any upstream provider with the same key.
+    pub fn add_provider(&mut self, mut provider: Provider) {
+        // The key is stored as the map key, so it survives serialization.
+        let key = std::mem::take(&mut provider.key);
+        self.add_providers.insert(key, provider);
+    }
+
+    /// Hide the upstream provider identified by `key`.
+    pub fn remove_provider<K>(&mut self, key: K)
+    where
+        K: Into<String>,
+    {
+        self.remove_providers.insert(key.into());
+    }
+
+    /// Add `endpoint` to the chain `chain_id` of the provider identified by
+    /// `provider`.
+    pub fn add_endpoint<P, E>(&mut self, provider: P, chain_id: u64, endpoint: E)
+    where
+        P: Into<String>,
+        E: Into<String>,
+    {
+        self.add_endpoints.push(EndpointOverride {
+            provider: provider.into(),
+            chain_id,
+            endpoint: endpoint.into(),
+        });
+    }
+
+    /// Remove `endpoint` from the chain `chain_id` of the provider identified
+    /// by `provider`.
+    pub fn remove_endpoint<P, E>(&mut self, provider: P, chain_id: u64, endpoint: E)
+    where
+        P: Into<String>,
+        E: Into<String>,
+    {
+        self.remove_endpoints.push(EndpointOverride {
+            provider: provider.into(),
+            chain_id,
+            endpoint: endpoint.into(),
+        });
+    }
+
+    /// Change the priority of the provider identified by `provider`.
+    pub fn set_priority<P>(&mut self, provider: P, priority: u32)
+    where
+        P: Into<String>,
+    {
+        self.priorities.insert(provider.into(), priority);
+    }
+
+    /// Apply these overrides to `list`, returning the overrides that had no
+    /// effect.
+    pub fn apply(&self, list: &mut RpcProviders) -> Vec<StaleOverride> {
+        let mut stale = Vec::new();
+        let mut providers = list.providers().to_vec();
+
+        for key in &self.remove_providers {
+            let before = providers.len();
+            providers.retain(|p| &p.key != key);
+
+            if before == providers.len() {
+                stale.push(StaleOverride::RemovedProviderMissing {
+                    provider: key.clone(),
+                });
+            }
+        }
+
+        for (key, added) in &self.add_providers {
+            providers.retain(|p| &p.key != key);
+            providers.push(Provider {
+                key: key.clone(),
+                ..added.clone()
+            });
+        }
+
+        for change in &self.remove_endpoints {
+            let chain = providers
+                .iter_mut()
+                .filter(|p| p.key == change.provider)
+                .flat_map(|p| p.chains.iter_mut())
+                .find(|c| c.chain_id == change.chain_id);
+
+            let removed = match chain {
+                Some(chain) => {
+                    let before = chain.endpoints.len();
+                    chain.endpoints.retain(|e| e != &change.endpoint);
+                    before != chain.endpoints.len()
+                }
+                None => false,
+            };
+
+            if !removed {
+                stale.push(StaleOverride::RemovedEndpointMissing(change.clone()));
+            }
+        }
+
+        for change in &self.add_endpoints {
+            let provider = match providers.iter_mut().find(|p| p.key == change.provider) {
+                Some(p) => p,
+                None => {
+                    stale.push(StaleOverride::AddedEndpointProviderMissing(change.clone()));
+                    continue;
+                }
+            };
+
+            let chain = match provider
+                .chains
+                .iter_mut()
+                .position(|c| c.chain_id == change.chain_id)
+            {
+                Some(idx) => &mut provider.chains[idx],
+                None => {
+                    provider
+                        .chains
+                        .push(ProviderChain::new(change.chain_id, None::<String>));
+                    provider.chains.last_mut().unwrap()
+                }
+            };
+
+            if chain.endpoints.contains(&change.endpoint) {
+                stale.push(StaleOverride::AddedEndpointPresent(change.clone()));
+            } else {
+                chain.endpoints.push(change.endpoint.clone());
+            }
+        }
+
+        for (key, priority) in &self.priorities {
+            match providers.iter_mut().find(|p| &p.key == key) {
+                Some(p) => p.priority = Some(*priority),
+                None => stale.push(StaleOverride::PriorityProviderMissing {
+                    provider: key.clone(),
+                }),
+            }
+        }
+
+        for provider in &mut providers {
+            provider.chains.retain(|c| !c.endpoints.is_empty());
+        }
+
+        providers.sort_by(|a, b| by_priority(a, b).then_with(|| a.key.cmp(&b.key)));
+        list.set_providers(providers);
+
+        stale
+    }
+}
@@ -0,0 +1,270 @@
+//! Restricting where lists may be retrieved from.
+//!
+//! Extension lists choose where their parent is retrieved from, so a
+//! resolver running on a server can be pointed at internal services. A
+//! [`SourcePolicy`] given to [`Resolver::policy`](crate::Resolver::policy)
+//! checks every [`Source`] before it is fetched, and resolution fails with
+//! [`Error::Policy`](crate::Error::Policy) if one is rejected.
+//!
+//! [`Rules`] is a configurable policy covering the common cases. Any
+//! `Fn(&Source) -> Result<(), PolicyError>` closure is a policy too.
+//!
+//! Hosts are compared ignoring case and a trailing `.`, and IPv4 addresses
+//! embedded in IPv6 addresses are checked as IPv4. A name that resolves to a
+//! private address isn't detected here, so a [`Fetch`](crate::Fetch)
+//! implementation exposed to untrusted lists should check the addresses it
+//! connects to as well.
+//!
+//! ## Example
+//!
+//! ```
+//! use eip5139::policy::Rules;
+//! use eip5139::Resolver;
+//! # use eip5139::errors::FetchError;
+//! # use eip5139::Source;
+//! # use std::future::Future;
+//! # use std::pin::Pin;
+//! # struct NoFetch;
+//! # impl eip5139::Fetch for NoFetch {
+//! #     fn fetch(&mut self, _: Source) ->
+//! #         Pin<Box<dyn Future<Output = Result<String, FetchError>>>>
+//! #     {
+//! #         unimplemented!()
+//! #     }
+//! # }
+//! # let fetch = NoFetch;
+//!
+//! let rules = Rules::new()
+//!     .allow_scheme("https")
+//!     .allow_scheme("ens")
+//!     .deny_host("*.internal.example.com")
+//!     .deny_private_addresses();
+//!
+//! let resolver = Resolver::new(fetch).policy(rules);
+//! ```
+
+use crate::errors::PolicyError;
+use crate::{Endpoint, Source};
+
+use std::collections::BTreeSet;
+use std::net::{IpAddr, Ipv4Addr};
+
+/// Decides whether a list may be retrieved from a [`Source`].
+pub trait SourcePolicy {
+    /// Check `source`, returning why it's rejected, if it is.
+    fn check(&self, source: &Source) -> Result<(), PolicyError>;
+}
+
+impl<F> SourcePolicy for F
+where
+    F: Fn(&Source) -> Result<(), PolicyError>,
+{
+    fn check(&self, source: &Source) -> Result<(), PolicyError> {
+        self(source)
+    }
+}
+
+/// Whether `host` matches `pattern`, which is either a host name or
+/// `*.` followed by a domain matching any of its subdomains.
+pub(crate) fn matches(pattern: &str, host: &str) -> bool {
+    match pattern.strip_prefix("*.") {
+        Some(domain) => host
+            .strip_suffix(domain)
+            .map(|rest| rest.ends_with('.'))
+            .unwrap_or(false),
+        None => pattern == host,
+    }
+}
+
+/// The host of `endpoint`, lowercased and without a trailing `.`, so every
+/// spelling of a name compares equal.
+pub(crate) fn host(endpoint: &Endpoint) -> String {
+    let host = endpoint.host().unwrap_or_default().to_ascii_lowercase();
+    match host.strip_suffix('.') {
+        Some(stripped) => stripped.to_owned(),
+        None => host,
+    }
+}
+
+/// Whether `host`, as returned by [`host`], names the local machine.
+pub(crate) fn is_local(host: &str) -> bool {
+    host.is_empty() || host == "localhost" || host.ends_with(".localhost")
+}
+
+/// Whether `ip` is reserved for private networks, the local machine, or
+/// anything else that isn't a public internet host.
+pub(crate) fn is_private(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_private_v4(ip),
+        IpAddr::V6(ip) => {
+            let segments = ip.segments();
+
+            // IPv4-mapped (`::ffff:a.b.c.d`), IPv4-compatible (`::a.b.c.d`)
+            // and NAT64 (`64:ff9b::a.b.c.d`) addresses.
+            if segments[..6] == [0, 0, 0, 0, 0, 0xffff]
+                || segments[..6] == [0, 0, 0, 0, 0, 0]
+                || segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0]
+            {
+                let [a, b] = segments[6].to_be_bytes();
+                let [c, d] = segments[7].to_be_bytes();
+                return is_private_v4(Ipv4Addr::new(a, b, c, d));
+            }
+
+            ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                // Unique local (`fc00::/7`.)
+                || (segments[0] & 0xfe00) == 0xfc00
+                // Link local (`fe80::/10`.)
+                || (segments[0] & 0xffc0) == 0xfe80
+                // Documentation (`2001:db8::/32`.)
+                || (segments[0] == 0x2001 && segments[1] == 0x0db8)
+        }
+    }
+}
+
+fn is_private_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+
+    ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        // "This network" (`0.0.0.0/8`.)
+        || a == 0
+        // Shared address space (`100.64.0.0/10`.)
+        || (a == 100 && (b & 0xc0) == 64)
+}
+
+/// A configurable [`SourcePolicy`].
+///
+/// A new `Rules` accepts every source. Each allow list only restricts
+/// sources once it has an entry, while deny lists always apply.
+#[derive(Debug, Clone, Default)]
+pub struct Rules {
+    schemes: BTreeSet<String>,
+    allowed_hosts: Vec<String>,
+    denied_hosts: Vec<String>,
+    ens_names: BTreeSet<String>,
+    deny_private: bool,
+}
+
+impl Rules {
+    /// Create rules that accept every source.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept URIs with `scheme` (eg. `https`), rejecting any other scheme.
+    ///
+    /// ENS names are treated as having the scheme `ens`, so once any scheme
+    /// is allowed, ENS names are rejected unless `ens` is allowed too.
+    pub fn allow_scheme<S>(mut self, scheme: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.schemes.insert(scheme.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Accept URIs whose host matches `pattern`, rejecting any other host.
+    ///
+    /// A pattern is either a host name, or `*.` followed by a domain to match
+    /// every subdomain of that domain (but not the domain itself.)
+    pub fn allow_host<S>(mut self, pattern: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.allowed_hosts.push(pattern.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Reject URIs whose host matches `pattern`. See
+    /// [`allow_host`](Self::allow_host) for the pattern syntax.
+    pub fn deny_host<S>(mut self, pattern: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.denied_hosts.push(pattern.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Accept the ENS name `name`, rejecting any other ENS name.
+    pub fn allow_ens<S>(mut self, name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.ens_names.insert(name.into().to_lowercase());
+        self
+    }
+
+    /// Reject URIs addressed to private, loopback, link local and other
+    /// non-public IP addresses, or to `localhost`.
+    pub fn deny_private_addresses(mut self) -> Self {
+        self.deny_private = true;
+        self
+    }
+
+    fn check_scheme(&self, scheme: &str) -> Result<(), PolicyError> {
+        if self.schemes.is_empty() || self.schemes.contains(scheme) {
+            Ok(())
+        } else {
+            Err(PolicyError::Scheme {
+                scheme: scheme.to_owned(),
+            })
+        }
+    }
+
+    fn check_uri(&self, uri: &str) -> Result<(), PolicyError> {
+        let endpoint = Endpoint::parse(uri).map_err(|e| PolicyError::Malformed {
+            message: e.to_string(),
+        })?;
+
+        self.check_scheme(endpoint.scheme().as_str())?;
+
+        let host = host(&endpoint);
+
+        if self.denied_hosts.iter().any(|p| matches(p, &host)) {
+            return Err(PolicyError::Host { host });
+        }
+
+        if !self.allowed_hosts.is_empty() && !self.allowed_hosts.iter().any(|p| matches(p, &host)) {
+            return Err(PolicyError::Host { host });
+        }
+
+        if self.deny_private {
+            if let Some(address) = endpoint.ip().filter(|ip| is_private(*ip)) {
+                return Err(PolicyError::PrivateAddress { address });
+            }
+
+            if is_local(&host) {
+                return Err(PolicyError::Host { host });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_ens(&self, name: &str) -> Result<(), PolicyError> {
+        self.check_scheme("ens")?;
+
+        let name = name.to_lowercase();
+        if self.ens_names.is_empty() || self.ens_names.contains(&name) {
+            Ok(())
+        } else {
+            Err(PolicyError::Ens { name })
+        }
+    }
+}
+
+impl SourcePolicy for Rules {
+    fn check(&self, source: &Source) -> Result<(), PolicyError> {
+        match source {
+            Source::Uri(uri) => self.check_uri(uri),
+            Source::Ens(name) => self.check_ens(name),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! Chain-centric lookups over an [`RpcProviders`](crate::RpcProviders).
+
+use crate::{Provider, ProviderChain};
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+/// Order providers by `priority`, with zero first and providers without a
+/// priority last.
+pub(crate) fn by_priority(a: &Provider, b: &Provider) -> Ordering {
+    match (a.priority, b.priority) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// An endpoint for a particular chain, along with the provider serving it.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct ChainEndpoint<'a> {
+    /// The provider serving this endpoint.
+    pub provider: &'a Provider,
+
+    /// The provider's entry for the chain.
+    pub chain: &'a ProviderChain,
+
+    /// The address of the endpoint.
+    pub endpoint: &'a str,
+}
+
+/// Location of a [`ProviderChain`] within a list of providers.
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    provider: usize,
+    chain: usize,
+}
+
+/// Maps each chain id to the providers serving it, in priority order.
+#[derive(Debug, Default)]
+pub(crate) struct ChainIndex {
+    chains: BTreeMap<u64, Vec<Entry>>,
+}
+
+impl ChainIndex {
+    pub(crate) fn new(providers: &[Provider]) -> Self {
+        let mut order: Vec<usize> = (0..providers.len()).collect();
+        order.sort_by(|a, b| by_priority(&providers[*a], &providers[*b]));
+
+        let mut chains = BTreeMap::<u64, Vec<Entry>>::new();
+
+        for provider in order {
+            for (chain, provider_chain) in providers[provider].chains.iter().enumerate() {
+                chains
+                    .entry(provider_chain.chain_id)
+                    .or_default()
+                    .push(Entry { provider, chain });
+            }
+        }
+
+        Self { chains }
+    }
+
+    pub(crate) fn chain_ids(&self) -> impl Iterator<Item = u64> + '_ {
+        self.chains.keys().copied()
+    }
+
+    pub(crate) fn chains<'a>(
+        &'a self,
+        providers: &'a [Provider],
+        chain_id: u64,
+    ) -> impl Iterator<Item = (&'a Provider, &'a ProviderChain)> + 'a {
+        self.chains
+            .get(&chain_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .map(move |e| {
+                let provider = &providers[e.provider];
+                (provider, &provider.chains[e.chain])
+            })
+    }
+}
@@ -1,88 +1,122 @@
 use crate::errors::{Error, JsonError, PatchError, ValidationError};
+use crate::events::{Event, Observer};
 use crate::fetch::Fetch;
-use crate::{RpcProviders, Source, Version};
+use crate::policy::SourcePolicy;
+use crate::query::by_priority;
+use crate::signature::TrustPolicy;
+use crate::timer::Timer;
+use crate::{
+    Endpoint, Provider, RangeGrammar, RpcProviders, Source, Timestamp, Version, VersionRange,
+};
 
 use jsonschema::JSONSchema;
 
 use lazy_static::lazy_static;
 
-use semver::Prerelease;
 use serde::{Deserialize, Serialize};
 
 use serde_json::Value;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+/// Maximum number of extension lists between the requested list and its root.
+const MAX_DEPTH: usize = 10;
+
+/// The schema, split by the kind of list it validates.
+///
+/// The schema's top-level `oneOf` has one branch for extension lists and one
+/// for root lists, and they're mutually exclusive on the presence of
+/// `extends`. Validating against the right branch directly is equivalent,
+/// and reports the errors within that branch instead of a single opaque
+/// `oneOf` failure.
+struct Schemas {
+    extension: JSONSchema,
+    root: JSONSchema,
+}
+
+impl Schemas {
+    /// Compile `schema`, asserting `format` keywords if the `formats` feature
+    /// is enabled.
+    fn compile(schema: &Value) -> Self {
+        let branch = |idx: usize| {
+            let mut schema = schema.clone();
+            let branch = schema["oneOf"][idx].take();
+            let object = schema.as_object_mut().unwrap();
+            object.remove("oneOf");
+            object.insert("allOf".into(), Value::Array(vec![branch]));
+
+            JSONSchema::options()
+                .should_validate_formats(cfg!(feature = "formats"))
+                .compile(&schema)
+                .unwrap()
+        };
+
+        Self {
+            extension: branch(0),
+            root: branch(1),
+        }
+    }
+
+    /// The schema validating `json`.
+    fn get(&self, json: &Value) -> &JSONSchema {
+        match json.get("extends") {
+            Some(_) => &self.extension,
+            None => &self.root,
+        }
+    }
+}
 
 lazy_static! {
-    static ref SCHEMA: JSONSchema = {
+    static ref SCHEMA: Schemas = {
         let raw = include_str!("schema.json");
         let json = serde_json::from_str(raw).unwrap();
-        JSONSchema::compile(&json).unwrap()
+        Schemas::compile(&json)
     };
-}
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(tag = "mode")]
-enum Mode {
-    #[serde(rename = "=")]
-    Exact {
-        #[serde(
-            default,
-            rename = "preRelease",
-            skip_serializing_if = "Option::is_none"
-        )]
-        pre_release: Option<String>,
-    },
-    #[serde(rename = "^")]
-    Caret,
-}
-
-impl Default for Mode {
-    fn default() -> Self {
-        Self::Caret
-    }
+    /// The EIP-5139 schema, relaxed to accept [`RangeGrammar::Extended`].
+    static ref EXTENDED_SCHEMA: Schemas = {
+        let raw = include_str!("schema.json");
+        let mut json: Value = serde_json::from_str(raw).unwrap();
+
+        json["$defs"]["VersionRange"]["oneOf"][0]["properties"]["mode"]["enum"] =
+            serde_json::json!(["^", "=", "~", ">", ">=", "<", "<="]);
+
+        json["oneOf"][0]["properties"]["extends"]["properties"]["version"] = serde_json::json!({
+            "oneOf": [
+                { "$ref": "#/$defs/VersionRange" },
+                {
+                    "type": "array",
+                    "minItems": 1,
+                    "items": { "$ref": "#/$defs/VersionRange" }
+                }
+            ]
+        });
+
+        Schemas::compile(&json)
+    };
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-struct VersionRange {
-    major: u64,
-    minor: u64,
-    patch: u64,
-    #[serde(flatten, default, skip_serializing_if = "Option::is_none")]
-    mode: Option<Mode>,
+/// One or more ranges, all of which must match the parent's version.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum Requirement {
+    One(VersionRange),
+    All(Vec<VersionRange>),
 }
 
-impl VersionRange {
-    fn into_semver(self) -> semver::VersionReq {
-        let op;
-        let pre;
-
-        match self.mode {
-            None | Some(Mode::Caret) => {
-                op = semver::Op::Caret;
-                pre = None;
-            }
-            Some(Mode::Exact { pre_release }) => {
-                op = semver::Op::Exact;
-                pre = pre_release.map(|p| Prerelease::new(&p).unwrap());
-            }
-        };
-
-        semver::VersionReq {
-            comparators: vec![semver::Comparator {
-                op,
-                pre: pre.unwrap_or(Prerelease::EMPTY),
-                major: self.major,
-                minor: Some(self.minor),
-                patch: Some(self.patch),
-            }],
+impl Requirement {
+    fn ranges(&self) -> &[VersionRange] {
+        match self {
+            Self::One(range) => std::slice::from_ref(range),
+            Self::All(ranges) => ranges,
         }
     }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct Extends {
-    version: VersionRange,
+    version: Requirement,
     #[serde(flatten)]
     from: Source,
 }
@@ -100,109 +134,341 @@ struct List {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     logo: Option<String>,
     version: Version,
-    timestamp: String,
+    timestamp: Timestamp,
 
     #[serde(flatten)]
     kind: Kind,
 }
 
 impl List {
-    fn check_version(&self, parent: &Self) -> Result<(), Error> {
-        let version_req = match self.kind {
-            Kind::Extension { ref extends, .. } => extends.version.clone().into_semver(),
+    /// Check this extension list's version requirement against `parent`,
+    /// returning an unsatisfied range on failure.
+    fn check_version(&self, parent: &Self) -> Result<(), VersionRange> {
+        let ranges = match self.kind {
+            Kind::Extension { ref extends, .. } => extends.version.ranges(),
             _ => panic!("only extension lists need a version check"),
         };
 
+        let version_req = semver::VersionReq {
+            comparators: ranges
+                .iter()
+                .cloned()
+                .map(VersionRange::into_comparator)
+                .collect(),
+        };
+
         let parent_version = parent.version.clone().into_semver();
 
         if version_req.matches(&parent_version) {
-            Ok(())
-        } else {
-            Err(Error::VersionMismatch {})
+            return Ok(());
         }
+
+        // Report the first range that doesn't match on its own, if any.
+        let unmatched = ranges
+            .iter()
+            .find(|&r| !r.clone().into_semver().matches(&parent_version))
+            .unwrap_or(&ranges[0]);
+
+        Err(unmatched.clone())
     }
 }
 
-pub async fn resolve(fetch: &mut dyn Fetch, source: Source) -> Result<RpcProviders, Error> {
-    let mut seen = HashSet::new();
+/// Retrieves a list and resolves any extension lists, with optional hooks.
+///
+/// [`RpcProviders::fetch`] is a shortcut for resolving with the default
+/// configuration.
+///
+/// ## Example
+///
+/// ```
+/// # use eip5139::{Fetch, Source};
+/// # use eip5139::errors::FetchError;
+/// # use std::future::Future;
+/// # use std::pin::Pin;
+/// # struct NoFetch;
+/// # impl Fetch for NoFetch {
+/// #     fn fetch(&mut self, _: Source) ->
+/// #         Pin<Box<dyn Future<Output = Result<String, FetchError>>>>
+/// #     {
+/// #         unimplemented!()
+/// #     }
+/// # }
+/// # let fetch = NoFetch;
+/// use eip5139::Resolver;
+///
+/// let future = Resolver::new(fetch)
+///     .observer(|event: &eip5139::events::Event<'_>| eprintln!("{:?}", event))
+///     .resolve(Source::Uri("https://example.com/list.json".into()));
+/// ```
+pub struct Resolver<'a, F> {
+    fetch: F,
+    observer: Option<Box<dyn Observer + 'a>>,
+    grammar: RangeGrammar,
+    trust: Option<TrustPolicy>,
+    policy: Option<Box<dyn SourcePolicy + 'a>>,
+}
 
-    let mut stack = Vec::<List>::new();
-    let mut current = source;
+impl<'a, F> fmt::Debug for Resolver<'a, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Resolver")
+            .field("observer", &self.observer.is_some())
+            .field("grammar", &self.grammar)
+            .field("trust", &self.trust)
+            .field("policy", &self.policy.is_some())
+            .finish_non_exhaustive()
+    }
+}
 
-    loop {
-        // Ensure that this `from` has not been seen before.
-        if let Some(duplicate) = seen.replace(current.clone()) {
-            return Err(Error::Cycle { duplicate });
+impl<'a, F> Resolver<'a, F>
+where
+    F: Fetch,
+{
+    /// Create a resolver that retrieves lists using `fetch`.
+    pub fn new(fetch: F) -> Self {
+        Self {
+            fetch,
+            observer: None,
+            grammar: RangeGrammar::default(),
+            trust: None,
+            policy: None,
         }
+    }
 
-        // Retrieve the parent list.
-        let text = fetch.fetch(current).await?;
-        let json = serde_json::from_str(&text).map_err(JsonError)?;
+    /// Require every list in the chain to be signed by a signer `policy`
+    /// trusts for its source.
+    ///
+    /// Signatures are retrieved with
+    /// [`Fetch::fetch_signature`](crate::Fetch::fetch_signature).
+    pub fn trust(mut self, policy: TrustPolicy) -> Self {
+        self.trust = Some(policy);
+        self
+    }
 
-        // Verify that the parent list is valid according to the JSON schema.
-        SCHEMA.validate(&json).map_err(ValidationError::new)?;
+    /// Check every source against `policy` before retrieving it, including
+    /// the requested list. See [`policy`](crate::policy).
+    pub fn policy<P>(mut self, policy: P) -> Self
+    where
+        P: SourcePolicy + 'a,
+    {
+        self.policy = Some(Box::new(policy));
+        self
+    }
 
-        // Parse the list.
-        let parent: List = serde_json::from_value(json).unwrap();
+    /// Choose which version range syntax extension lists may use.
+    ///
+    /// Defaults to [`RangeGrammar::Strict`].
+    pub fn range_grammar(mut self, grammar: RangeGrammar) -> Self {
+        self.grammar = grammar;
+        self
+    }
 
-        // Ensure that the parent list is version compatible.
-        if let Some(child) = stack.last() {
-            child.check_version(&parent)?;
-        }
+    fn schema(&self, json: &Value) -> &'static JSONSchema {
+        let schemas: &'static Schemas = match self.grammar {
+            RangeGrammar::Strict => &SCHEMA,
+            RangeGrammar::Extended => &EXTENDED_SCHEMA,
+        };
 
-        stack.push(parent);
+        schemas.get(json)
+    }
 
-        // Is the current list an extension list?
-        current = match &stack.last().unwrap().kind {
-            Kind::Extension { extends, .. } => extends.from.clone(),
-            Kind::Root { .. } => break,
-        };
+    /// Report each step of resolution to `observer`.
+    pub fn observer<O>(mut self, observer: O) -> Self
+    where
+        O: Observer + 'a,
+    {
+        self.observer = Some(Box::new(observer));
+        self
+    }
 
-        if stack.len() > 10 {
-            return Err(Error::TooDeep {});
+    fn emit(&mut self, event: Event<'_>) {
+        if let Some(observer) = self.observer.as_mut() {
+            observer.observe(&event);
         }
     }
 
-    let mut output = stack.pop().unwrap();
+    /// Retrieve the list from `source` and resolve any extension lists.
+    pub async fn resolve(mut self, source: Source) -> Result<RpcProviders, Error> {
+        let mut seen = HashSet::new();
+        let mut chain = Vec::new();
 
-    for mut list in stack.into_iter() {
-        let patch = match list.kind {
-            Kind::Extension { changes, .. } => json_patch::from_value(changes).unwrap(),
-            _ => unreachable!(),
-        };
+        let mut stack = Vec::<(Source, List)>::new();
+        let mut current = source;
 
-        list.kind = output.kind;
-        output = list;
+        loop {
+            // Ensure that this `from` has not been seen before.
+            if let Some(duplicate) = seen.replace(current.clone()) {
+                return Err(Error::Cycle { duplicate, chain });
+            }
+
+            chain.push(current.clone());
+
+            if let Some(ref policy) = self.policy {
+                policy.check(&current).map_err(|source| Error::Policy {
+                    list: current.clone(),
+                    source,
+                })?;
+            }
+
+            // Retrieve the parent list.
+            self.emit(Event::FetchStarted { source: &current });
+            let timer = Timer::start();
+            let text = self.fetch.fetch(current.clone()).await?;
+            self.emit(Event::FetchFinished {
+                source: &current,
+                elapsed: timer.elapsed(),
+            });
+
+            let json = serde_json::from_str(&text).map_err(JsonError)?;
+
+            // Authenticate the list before looking at it any further.
+            if let Some(ref policy) = self.trust {
+                let timer = Timer::start();
+                let signature = self.fetch.fetch_signature(current.clone()).await?;
+                policy.verify(&current, &json, signature.as_deref())?;
+                self.emit(Event::SignatureVerified {
+                    source: &current,
+                    elapsed: timer.elapsed(),
+                });
+            }
+
+            // Verify that the parent list is valid according to the JSON schema.
+            let timer = Timer::start();
+            self.schema(&json)
+                .validate(&json)
+                .map_err(ValidationError::new)?;
+            self.emit(Event::Validated {
+                source: &current,
+                elapsed: timer.elapsed(),
+            });
+
+            // Parse the list.
+            let parent: List = serde_json::from_value(json).map_err(JsonError)?;
+
+            // Ensure that the parent list is version compatible.
+            if let Some((child_source, child)) = stack.last() {
+                let timer = Timer::start();
+                child
+                    .check_version(&parent)
+                    .map_err(|requested| Error::VersionMismatch {
+                        child: child_source.clone(),
+                        requested: Box::new(requested),
+                        parent: current.clone(),
+                        actual: Box::new(parent.version.clone()),
+                    })?;
+
+                if child.timestamp < parent.timestamp {
+                    return Err(Error::TimestampOrder {
+                        child: child_source.clone(),
+                        timestamp: Box::new(child.timestamp.clone()),
+                        parent: current.clone(),
+                        parent_timestamp: Box::new(parent.timestamp.clone()),
+                    });
+                }
+
+                self.emit(Event::VersionChecked {
+                    source: child_source,
+                    parent: &current,
+                    elapsed: timer.elapsed(),
+                });
+            }
+
+            // Is the current list an extension list?
+            let next = match &parent.kind {
+                Kind::Extension { extends, .. } => Some(extends.from.clone()),
+                Kind::Root { .. } => None,
+            };
+
+            stack.push((current, parent));
+
+            current = match next {
+                Some(next) => next,
+                None => break,
+            };
+
+            if stack.len() > MAX_DEPTH {
+                chain.push(current);
+                return Err(Error::TooDeep {
+                    chain,
+                    limit: MAX_DEPTH,
+                });
+            }
+        }
+
+        let (_, mut output) = stack.pop().unwrap();
+
+        for (source, mut list) in stack.into_iter().rev() {
+            let patch = match list.kind {
+                Kind::Extension { changes, .. } => json_patch::from_value(changes).unwrap(),
+                _ => unreachable!(),
+            };
+
+            list.kind = output.kind;
+            output = list;
+
+            let providers = match output.kind {
+                Kind::Root { ref mut providers } => providers,
+                _ => unreachable!(),
+            };
+
+            let timer = Timer::start();
+            json_patch::patch(providers, &patch).map_err(PatchError)?;
+            self.emit(Event::PatchApplied {
+                source: &source,
+                elapsed: timer.elapsed(),
+            });
+
+            // Verify that the list is valid according to the JSON schema.
+            let timer = Timer::start();
+            let json = serde_json::to_value(&output).unwrap();
+            self.schema(&json)
+                .validate(&json)
+                .map_err(ValidationError::new)?;
+            self.emit(Event::Validated {
+                source: &source,
+                elapsed: timer.elapsed(),
+            });
+        }
 
         let providers = match output.kind {
-            Kind::Root { ref mut providers } => providers,
+            Kind::Root { providers } => providers,
             _ => unreachable!(),
         };
 
-        json_patch::patch(providers, &patch).map_err(PatchError)?;
+        // The schema accepts integers written with a fraction (eg. `1.0`),
+        // which don't deserialize, so this can still fail.
+        let mut providers = serde_json::from_value::<BTreeMap<String, Value>>(providers)
+            .unwrap()
+            .into_iter()
+            .map(|(key, v)| serde_json::from_value(v).map(|provider| Provider { key, ..provider }))
+            .collect::<Result<Vec<Provider>, _>>()
+            .map_err(JsonError)?;
+
+        // Keys are already in order, and the sort is stable.
+        providers.sort_by(by_priority);
+
+        // Parse every endpoint, so non-URIs are reported precisely even when
+        // the schema's `format` assertions don't catch them.
+        let endpoints = providers
+            .iter()
+            .flat_map(|p| p.chains.iter())
+            .flat_map(|c| c.endpoints.iter());
+
+        for endpoint in endpoints {
+            if let Err(source) = Endpoint::parse(endpoint) {
+                return Err(Error::Endpoint {
+                    endpoint: endpoint.clone(),
+                    source,
+                });
+            }
+        }
 
-        // Verify that the list is valid according to the JSON schema.
-        let json = serde_json::to_value(&output).unwrap();
-        println!("{:#?}", json);
-        SCHEMA.validate(&json).map_err(ValidationError::new)?;
+        Ok(RpcProviders {
+            name: output.name,
+            logo: output.logo,
+            version: output.version,
+            timestamp: output.timestamp,
+            providers,
+            index: Default::default(),
+        })
     }
-
-    let providers = match output.kind {
-        Kind::Root { providers } => providers,
-        _ => unreachable!(),
-    };
-
-    let providers = serde_json::from_value::<HashMap<String, Value>>(providers)
-        .unwrap()
-        .into_iter()
-        .map(|(_, v)| serde_json::from_value(v).unwrap())
-        .collect();
-
-    Ok(RpcProviders {
-        name: output.name,
-        logo: output.logo,
-        version: output.version,
-        timestamp: output.timestamp,
-        providers,
-    })
 }
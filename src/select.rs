@@ -0,0 +1,281 @@
+//! Choosing an endpoint for a chain from a resolved list.
+//!
+//! A [`Selector`] pairs an [`RpcProviders`] with a [`Strategy`]. Strategies
+//! receive every endpoint for the requested chain, ordered by provider
+//! [`priority`](crate::Provider::priority), and pick one of them.
+//!
+//! ## Example
+//!
+//! ```
+//! use eip5139::select::{RoundRobin, Selector};
+//! # use eip5139::RpcProviders;
+//! # let list = RpcProviders::default();
+//!
+//! let mut selector = Selector::new(&list, RoundRobin::default());
+//!
+//! if let Some(choice) = selector.select(137) {
+//!     println!("using {} from {}", choice.endpoint, choice.provider.name);
+//! }
+//! ```
+
+use crate::query::{by_priority, ChainEndpoint};
+use crate::RpcProviders;
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Source of randomness for strategies like [`WeightedRandom`].
+///
+/// Implemented for any `FnMut() -> u64` closure, so tests can supply a fixed
+/// sequence.
+pub trait Random {
+    /// Return the next uniformly distributed random number.
+    fn next_u64(&mut self) -> u64;
+}
+
+impl<F> Random for F
+where
+    F: FnMut() -> u64,
+{
+    fn next_u64(&mut self) -> u64 {
+        self()
+    }
+}
+
+/// A small, fast, non-cryptographic random number generator (xorshift64*).
+#[derive(Debug, Clone)]
+pub struct XorShift(u64);
+
+impl XorShift {
+    /// Create a generator from `seed`.
+    pub fn new(seed: u64) -> Self {
+        // Xorshift gets stuck on zero.
+        Self(if seed == 0 {
+            0x9E37_79B9_7F4A_7C15
+        } else {
+            seed
+        })
+    }
+}
+
+impl Random for XorShift {
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+}
+
+/// Picks one endpoint from the candidates for a chain.
+pub trait Strategy {
+    /// Return the index of the chosen endpoint in `candidates`, or `None` to
+    /// choose nothing.
+    ///
+    /// `candidates` is never empty, and is ordered by provider priority.
+    fn select(&mut self, chain_id: u64, candidates: &[ChainEndpoint<'_>]) -> Option<usize>;
+}
+
+/// The number of leading candidates that share the highest priority.
+fn top_tier(candidates: &[ChainEndpoint<'_>]) -> usize {
+    let first = candidates[0].provider;
+    candidates
+        .iter()
+        .take_while(|c| by_priority(first, c.provider) == Ordering::Equal)
+        .count()
+}
+
+/// Always choose the first endpoint of the highest priority provider.
+#[derive(Debug, Default, Clone)]
+pub struct StrictPriority;
+
+impl Strategy for StrictPriority {
+    fn select(&mut self, _: u64, _: &[ChainEndpoint<'_>]) -> Option<usize> {
+        Some(0)
+    }
+}
+
+/// Choose randomly, favouring higher priority providers.
+///
+/// A provider with priority `p` is weighted `1 / (p + 1)`. Providers without
+/// a priority are weighted as if they had the lowest priority in the list,
+/// plus one. The weight of a provider is shared between its endpoints.
+#[derive(Debug, Clone)]
+pub struct WeightedRandom<R> {
+    random: R,
+}
+
+impl<R> WeightedRandom<R>
+where
+    R: Random,
+{
+    /// Create a strategy drawing random numbers from `random`.
+    pub fn new(random: R) -> Self {
+        Self { random }
+    }
+}
+
+impl<R> Strategy for WeightedRandom<R>
+where
+    R: Random,
+{
+    fn select(&mut self, _: u64, candidates: &[ChainEndpoint<'_>]) -> Option<usize> {
+        let lowest = candidates
+            .iter()
+            .filter_map(|c| c.provider.priority)
+            .max()
+            .map(|p| p.saturating_add(1))
+            .unwrap_or(0);
+
+        let weights: Vec<f64> = candidates
+            .iter()
+            .map(|c| {
+                let priority = c.provider.priority.unwrap_or(lowest);
+                let share = c.chain.endpoints.len().max(1) as f64;
+                1.0 / (f64::from(priority) + 1.0) / share
+            })
+            .collect();
+
+        let total: f64 = weights.iter().sum();
+
+        // 53 random bits give a uniform float in [0, 1).
+        let unit = (self.random.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        let mut target = unit * total;
+
+        for (idx, weight) in weights.iter().enumerate() {
+            if target < *weight {
+                return Some(idx);
+            }
+            target -= weight;
+        }
+
+        Some(candidates.len() - 1)
+    }
+}
+
+/// Rotate through the endpoints of the highest priority providers.
+///
+/// Each chain is rotated independently.
+#[derive(Debug, Default, Clone)]
+pub struct RoundRobin {
+    next: HashMap<u64, usize>,
+}
+
+impl Strategy for RoundRobin {
+    fn select(&mut self, chain_id: u64, candidates: &[ChainEndpoint<'_>]) -> Option<usize> {
+        let tier = top_tier(candidates);
+        let next = self.next.entry(chain_id).or_insert(0);
+        let choice = *next % tier;
+        *next = choice + 1;
+        Some(choice)
+    }
+}
+
+/// Keep choosing the same endpoint for a chain once one has been chosen.
+///
+/// Use one `Sticky` per session. The wrapped strategy is consulted when a
+/// chain has no endpoint yet, or when the remembered endpoint is no longer
+/// listed.
+#[derive(Debug, Default, Clone)]
+pub struct Sticky<S> {
+    inner: S,
+    chosen: HashMap<u64, String>,
+}
+
+impl<S> Sticky<S>
+where
+    S: Strategy,
+{
+    /// Remember the choices made by `inner`.
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            chosen: HashMap::new(),
+        }
+    }
+
+    /// Forget the endpoint chosen for `chain_id`, so the next selection
+    /// consults the wrapped strategy.
+    pub fn forget(&mut self, chain_id: u64) {
+        self.chosen.remove(&chain_id);
+    }
+
+    /// Forget every chosen endpoint, starting a new session.
+    pub fn reset(&mut self) {
+        self.chosen.clear();
+    }
+}
+
+impl<S> Strategy for Sticky<S>
+where
+    S: Strategy,
+{
+    fn select(&mut self, chain_id: u64, candidates: &[ChainEndpoint<'_>]) -> Option<usize> {
+        if let Some(chosen) = self.chosen.get(&chain_id) {
+            if let Some(idx) = candidates.iter().position(|c| c.endpoint == chosen) {
+                return Some(idx);
+            }
+        }
+
+        let idx = self.inner.select(chain_id, candidates)?;
+        self.chosen
+            .insert(chain_id, candidates[idx].endpoint.to_owned());
+        Some(idx)
+    }
+}
+
+/// Chooses endpoints from an [`RpcProviders`] using a [`Strategy`].
+pub struct Selector<'a, S> {
+    providers: &'a RpcProviders,
+    strategy: S,
+}
+
+impl<'a, S> fmt::Debug for Selector<'a, S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Selector")
+            .field("providers", &self.providers.name)
+            .field("strategy", &self.strategy)
+            .finish()
+    }
+}
+
+impl<'a, S> Selector<'a, S>
+where
+    S: Strategy,
+{
+    /// Create a selector choosing from `providers` using `strategy`.
+    pub fn new(providers: &'a RpcProviders, strategy: S) -> Self {
+        Self {
+            providers,
+            strategy,
+        }
+    }
+
+    /// Get the strategy used by this selector.
+    pub fn strategy(&self) -> &S {
+        &self.strategy
+    }
+
+    /// Get a mutable reference to the strategy used by this selector.
+    pub fn strategy_mut(&mut self) -> &mut S {
+        &mut self.strategy
+    }
+
+    /// Choose an endpoint for the chain identified by `chain_id`.
+    ///
+    /// Returns `None` if no provider serves the chain.
+    pub fn select(&mut self, chain_id: u64) -> Option<ChainEndpoint<'a>> {
+        let candidates: Vec<_> = self.providers.endpoints_for(chain_id).collect();
+
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let idx = self.strategy.select(chain_id, &candidates)?;
+        candidates.get(idx).copied()
+    }
+}
@@ -0,0 +1,241 @@
+//! Authenticating lists by their publisher.
+//!
+//! A publisher signs the canonical JSON of each list document they publish
+//! (sorted keys, no whitespace; see [RFC 8785]), and serves the signature
+//! next to the list. [`Fetch::fetch_signature`](crate::Fetch::fetch_signature)
+//! retrieves it.
+//!
+//! Signatures are hex encoded (with or without a `0x` prefix), and take one
+//! of two forms:
+//!
+//! * 65 bytes: a secp256k1 signature (`r || s || v`) over the [EIP-191]
+//!   personal message containing the canonical JSON, as produced by
+//!   `personal_sign` and most Ethereum tooling.
+//! * 64 bytes: an ed25519 signature over the canonical JSON. Requires the
+//!   `ed25519` feature.
+//!
+//! A [`TrustPolicy`] lists the [`Signer`]s allowed for each [`Source`]. When
+//! resolving with a policy, every list in the chain must be signed by one of
+//! the signers trusted for its source.
+//!
+//! ## Example
+//!
+//! ```
+//! use eip5139::signature::{Signer, TrustPolicy};
+//! use eip5139::{Resolver, Source};
+//! # use eip5139::errors::FetchError;
+//! # use std::future::Future;
+//! # use std::pin::Pin;
+//! # struct NoFetch;
+//! # impl eip5139::Fetch for NoFetch {
+//! #     fn fetch(&mut self, _: Source) ->
+//! #         Pin<Box<dyn Future<Output = Result<String, FetchError>>>>
+//! #     {
+//! #         unimplemented!()
+//! #     }
+//! # }
+//! # let fetch = NoFetch;
+//!
+//! let source = Source::Uri("https://example.com/list.json".into());
+//! let signer: Signer = "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf".parse().unwrap();
+//!
+//! let policy = TrustPolicy::new().trust(source.clone(), signer);
+//! let future = Resolver::new(fetch).trust(policy).resolve(source);
+//! ```
+//!
+//! [RFC 8785]: https://www.rfc-editor.org/rfc/rfc8785
+//! [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+
+use crate::canonical;
+use crate::errors::{Error, SignatureError};
+use crate::Source;
+
+use k256::ecdsa::recoverable;
+use k256::ecdsa::VerifyingKey;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+use serde_json::Value;
+
+use sha3::{Digest, Keccak256};
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// A key trusted to sign lists.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Signer {
+    /// An Ethereum address, for secp256k1 signatures.
+    ///
+    /// Written as `0x` followed by 40 hex digits.
+    Address([u8; 20]),
+
+    /// An ed25519 public key.
+    ///
+    /// Written as `ed25519:` followed by 64 hex digits.
+    #[cfg(feature = "ed25519")]
+    Ed25519([u8; 32]),
+}
+
+impl fmt::Display for Signer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Address(address) => write!(f, "0x{}", hex::encode(address)),
+            #[cfg(feature = "ed25519")]
+            Self::Ed25519(key) => write!(f, "ed25519:{}", hex::encode(key)),
+        }
+    }
+}
+
+impl FromStr for Signer {
+    type Err = SignatureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        #[cfg(feature = "ed25519")]
+        if let Some(key) = s.strip_prefix("ed25519:") {
+            return decode(key).map(Self::Ed25519);
+        }
+
+        match s.strip_prefix("0x") {
+            Some(address) => decode(address).map(Self::Address),
+            None => Err(SignatureError::malformed(format!(
+                "`{}` is not an address or key",
+                s
+            ))),
+        }
+    }
+}
+
+/// Decode exactly `N` hex encoded bytes.
+fn decode<const N: usize>(text: &str) -> Result<[u8; N], SignatureError> {
+    let mut bytes = [0; N];
+    hex::decode_to_slice(text, &mut bytes)
+        .map_err(|e| SignatureError::malformed(format!("`{}`: {}", text, e)))?;
+    Ok(bytes)
+}
+
+/// The signers trusted for each [`Source`].
+#[derive(Debug, Clone, Default)]
+pub struct TrustPolicy {
+    signers: HashMap<Source, Vec<Signer>>,
+}
+
+impl TrustPolicy {
+    /// Create a policy that trusts no one.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept lists from `source` signed by `signer`.
+    pub fn trust(mut self, source: Source, signer: Signer) -> Self {
+        self.signers.entry(source).or_default().push(signer);
+        self
+    }
+
+    /// The signers trusted for `source`.
+    pub fn signers(&self, source: &Source) -> &[Signer] {
+        self.signers
+            .get(source)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Check `signature` over `document`, retrieved from `list`.
+    pub(crate) fn verify(
+        &self,
+        list: &Source,
+        document: &Value,
+        signature: Option<&str>,
+    ) -> Result<(), Error> {
+        let signature = match signature {
+            Some(s) => s.trim(),
+            None => return Err(Error::Unsigned { list: list.clone() }),
+        };
+
+        let message = canonical::to_string(document);
+
+        self.check(list, message.as_bytes(), signature)
+            .map_err(|source| Error::BadSignature {
+                list: list.clone(),
+                source,
+            })
+    }
+
+    fn check(&self, list: &Source, message: &[u8], signature: &str) -> Result<(), SignatureError> {
+        let signature = signature.strip_prefix("0x").unwrap_or(signature);
+        let bytes = hex::decode(signature)
+            .map_err(|e| SignatureError::malformed(format!("not hex: {}", e)))?;
+        let trusted = self.signers(list);
+
+        match bytes.len() {
+            65 => {
+                let signer = Signer::Address(recover(message, &bytes)?);
+                if trusted.contains(&signer) {
+                    Ok(())
+                } else {
+                    Err(SignatureError::Untrusted {
+                        signer: signer.to_string(),
+                    })
+                }
+            }
+
+            #[cfg(feature = "ed25519")]
+            64 => {
+                use ed25519_dalek::{PublicKey, Signature, Verifier};
+
+                let signature =
+                    Signature::from_bytes(&bytes).map_err(|_| SignatureError::Invalid)?;
+
+                let verified = trusted.iter().any(|signer| match signer {
+                    Signer::Ed25519(key) => PublicKey::from_bytes(key)
+                        .map(|k| k.verify(message, &signature).is_ok())
+                        .unwrap_or(false),
+                    _ => false,
+                });
+
+                if verified {
+                    Ok(())
+                } else {
+                    Err(SignatureError::Invalid)
+                }
+            }
+
+            other => Err(SignatureError::malformed(format!(
+                "unexpected signature length ({} bytes)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Recover the address that signed the EIP-191 personal message `message`.
+fn recover(message: &[u8], signature: &[u8]) -> Result<[u8; 20], SignatureError> {
+    let mut signature = signature.to_vec();
+
+    // Ethereum tooling encodes the recovery id as 27 or 28.
+    if signature[64] >= 27 {
+        signature[64] -= 27;
+    }
+
+    let signature =
+        recoverable::Signature::try_from(&signature[..]).map_err(|_| SignatureError::Invalid)?;
+
+    let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    prefixed.extend_from_slice(message);
+
+    let key = signature
+        .recover_verifying_key(&prefixed)
+        .map_err(|_| SignatureError::Invalid)?;
+
+    Ok(address(&key))
+}
+
+fn address(key: &VerifyingKey) -> [u8; 20] {
+    let point = key.to_encoded_point(false);
+    let hash = Keccak256::digest(&point.as_bytes()[1..]);
+
+    let mut address = [0; 20];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
@@ -0,0 +1,268 @@
+//! Keeping a list up to date.
+//!
+//! A [`Subscription`] holds the [`Source`] of a list and the most recently
+//! resolved copy. Each [`refresh`](Subscription::refresh) re-resolves the
+//! list and describes what changed as an [`Update`], using a [`Diff`].
+//!
+//! Following EIP-5139's use of semantic versioning, a new major version may
+//! remove providers the user relies on, so it isn't installed until the
+//! wallet [`accept`](Subscription::accept)s it (typically after asking the
+//! user.) The same goes for an older version, so a list can't be silently
+//! rolled back.
+//!
+//! ## Example
+//!
+//! ```
+//! use eip5139::subscription::Subscription;
+//! use eip5139::Source;
+//! # use eip5139::errors::FetchError;
+//! # use std::future::Future;
+//! # use std::pin::Pin;
+//! # struct NoFetch;
+//! # impl eip5139::Fetch for NoFetch {
+//! #     fn fetch(&mut self, _: Source) ->
+//! #         Pin<Box<dyn Future<Output = Result<String, FetchError>>>>
+//! #     {
+//! #         unimplemented!()
+//! #     }
+//! # }
+//! # let fetch = NoFetch;
+//!
+//! let source = Source::Ens("example.eth".into());
+//! let mut subscription = Subscription::new(fetch, source);
+//!
+//! let future = async move {
+//!     let update = subscription.refresh().await?;
+//!
+//!     if update.requires_consent {
+//!         // Ask the user, then:
+//!         subscription.accept();
+//!     }
+//!
+//!     print!("{}", update.diff);
+//!
+//!     Ok::<_, eip5139::Error>(())
+//! };
+//! ```
+
+use crate::diff::Diff;
+use crate::errors::Error;
+use crate::fetch::Fetch;
+use crate::policy::SourcePolicy;
+use crate::signature::TrustPolicy;
+use crate::{RangeGrammar, Resolver, RpcProviders, Source};
+
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+pub use crate::diff::Bump;
+
+/// How often [`Subscription::poll`] refreshes a list, unless configured.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// The outcome of refreshing a [`Subscription`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Update {
+    /// What changed since the current list. Empty on the first refresh.
+    pub diff: Diff,
+
+    /// Whether the new list is waiting for
+    /// [`accept`](Subscription::accept) instead of replacing the current
+    /// list.
+    pub requires_consent: bool,
+}
+
+/// Tracks a list over time. See the [module documentation](self).
+pub struct Subscription<F> {
+    fetch: F,
+    source: Source,
+    grammar: RangeGrammar,
+    trust: Option<TrustPolicy>,
+    policy: Option<Box<dyn SourcePolicy>>,
+    interval: Duration,
+    last_attempt: Option<SystemTime>,
+    current: Option<RpcProviders>,
+    pending: Option<RpcProviders>,
+}
+
+impl<F> fmt::Debug for Subscription<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Subscription")
+            .field("source", &self.source)
+            .field("grammar", &self.grammar)
+            .field("trust", &self.trust)
+            .field("policy", &self.policy.is_some())
+            .field("interval", &self.interval)
+            .field("last_attempt", &self.last_attempt)
+            .field("current", &self.current)
+            .field("pending", &self.pending)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F> Subscription<F>
+where
+    F: Fetch,
+{
+    /// Create a subscription to the list at `source`, retrieved using
+    /// `fetch`.
+    ///
+    /// Nothing is retrieved until the first refresh.
+    pub fn new(fetch: F, source: Source) -> Self {
+        Self {
+            fetch,
+            source,
+            grammar: RangeGrammar::default(),
+            trust: None,
+            policy: None,
+            interval: DEFAULT_INTERVAL,
+            last_attempt: None,
+            current: None,
+            pending: None,
+        }
+    }
+
+    /// Choose which version range syntax extension lists may use.
+    ///
+    /// Defaults to [`RangeGrammar::Strict`].
+    pub fn range_grammar(mut self, grammar: RangeGrammar) -> Self {
+        self.grammar = grammar;
+        self
+    }
+
+    /// Require every list in the chain to be signed by a signer `policy`
+    /// trusts for its source, on every refresh. See
+    /// [`Resolver::trust`].
+    pub fn trust(mut self, policy: TrustPolicy) -> Self {
+        self.trust = Some(policy);
+        self
+    }
+
+    /// Check every source against `policy` before retrieving it, on every
+    /// refresh. See [`Resolver::policy`].
+    pub fn policy<P>(mut self, policy: P) -> Self
+    where
+        P: SourcePolicy + 'static,
+    {
+        self.policy = Some(Box::new(policy));
+        self
+    }
+
+    /// Set how often [`poll`](Self::poll) refreshes the list.
+    ///
+    /// Defaults to [`DEFAULT_INTERVAL`].
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// The source of the list.
+    pub fn source(&self) -> &Source {
+        &self.source
+    }
+
+    /// The list currently in use, if it has been retrieved.
+    pub fn current(&self) -> Option<&RpcProviders> {
+        self.current.as_ref()
+    }
+
+    /// A new major version, or a downgrade, waiting for
+    /// [`accept`](Self::accept).
+    pub fn pending(&self) -> Option<&RpcProviders> {
+        self.pending.as_ref()
+    }
+
+    /// Replace the current list with the pending one, returning whether there
+    /// was one.
+    pub fn accept(&mut self) -> bool {
+        match self.pending.take() {
+            Some(pending) => {
+                self.current = Some(pending);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Discard the pending list, if any.
+    ///
+    /// The same version will be offered again by the next refresh.
+    pub fn reject(&mut self) -> Option<RpcProviders> {
+        self.pending.take()
+    }
+
+    /// Whether the list should be refreshed at `now`.
+    pub fn is_due(&self, now: SystemTime) -> bool {
+        match self.last_attempt {
+            None => true,
+            Some(last) => match now.duration_since(last) {
+                Ok(elapsed) => elapsed >= self.interval,
+                Err(_) => false,
+            },
+        }
+    }
+
+    /// Refresh the list if it [`is_due`](Self::is_due) at `now`.
+    ///
+    /// Call this periodically (eg. from a timer) to follow a schedule.
+    pub async fn poll(&mut self, now: SystemTime) -> Option<Result<Update, Error>> {
+        if !self.is_due(now) {
+            return None;
+        }
+
+        self.last_attempt = Some(now);
+        Some(self.resolve().await)
+    }
+
+    /// Re-resolve the list now, and compare it to the current list.
+    ///
+    /// Fails with [`Error::TimestampRegression`] if the new list is a newer
+    /// version with an older timestamp, leaving the current list in place.
+    pub async fn refresh(&mut self) -> Result<Update, Error> {
+        self.last_attempt = Some(crate::timer::now());
+        self.resolve().await
+    }
+
+    async fn resolve(&mut self) -> Result<Update, Error> {
+        let mut resolver = Resolver::new(&mut self.fetch).range_grammar(self.grammar);
+
+        if let Some(ref trust) = self.trust {
+            resolver = resolver.trust(trust.clone());
+        }
+
+        if let Some(ref policy) = self.policy {
+            resolver = resolver.policy(|source: &Source| policy.check(source));
+        }
+
+        let list = resolver.resolve(self.source.clone()).await?;
+
+        let current = match self.current {
+            Some(ref current) => current,
+            None => {
+                self.current = Some(list);
+                return Ok(Update {
+                    diff: Diff::default(),
+                    requires_consent: false,
+                });
+            }
+        };
+
+        current.check_update(&list)?;
+
+        let diff = Diff::between(current, &list);
+        let update = Update {
+            requires_consent: diff.bump().requires_consent(),
+            diff,
+        };
+
+        if update.requires_consent {
+            self.pending = Some(list);
+        } else {
+            self.pending = None;
+            self.current = Some(list);
+        }
+
+        Ok(update)
+    }
+}
@@ -0,0 +1,50 @@
+//! Portable elapsed time measurement.
+//!
+//! `std::time::Instant` and `SystemTime::now` panic on
+//! `wasm32-unknown-unknown`, so the browser's clock is used there instead.
+
+use std::time::{Duration, SystemTime};
+
+/// The current wall clock time.
+#[cfg(not(target_family = "wasm"))]
+pub(crate) fn now() -> SystemTime {
+    SystemTime::now()
+}
+
+/// The current wall clock time.
+#[cfg(target_family = "wasm")]
+pub(crate) fn now() -> SystemTime {
+    let millis = js_sys::Date::now().max(0.0);
+    SystemTime::UNIX_EPOCH + Duration::from_secs_f64(millis / 1000.0)
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Timer(std::time::Instant);
+
+#[cfg(not(target_family = "wasm"))]
+impl Timer {
+    pub(crate) fn start() -> Self {
+        Self(std::time::Instant::now())
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        self.0.elapsed()
+    }
+}
+
+#[cfg(target_family = "wasm")]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Timer(f64);
+
+#[cfg(target_family = "wasm")]
+impl Timer {
+    pub(crate) fn start() -> Self {
+        Self(js_sys::Date::now())
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        let millis = (js_sys::Date::now() - self.0).max(0.0);
+        Duration::from_secs_f64(millis / 1000.0)
+    }
+}
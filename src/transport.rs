@@ -0,0 +1,101 @@
+//! Sending JSON-RPC requests to endpoints.
+
+use crate::errors::{RpcError, TransportError};
+
+use serde_json::{json, Value};
+
+use std::future::Future;
+use std::pin::Pin;
+
+/// Used to deliver JSON-RPC requests to endpoints.
+///
+/// Implementations receive a complete JSON-RPC request object, and should
+/// return the complete response object from the endpoint. Decoding results
+/// and errors is handled by this crate.
+///
+/// ## Example
+///
+/// ```
+/// use eip5139::errors::TransportError;
+/// use eip5139::transport::Transport;
+///
+/// use serde_json::{json, Value};
+///
+/// use std::future::Future;
+/// use std::pin::Pin;
+///
+/// /// Pretends every endpoint serves Ethereum mainnet at block 100.
+/// struct Mainnet;
+///
+/// impl Transport for Mainnet {
+///     fn send(&mut self, _endpoint: &str, request: Value) ->
+///         Pin<Box<dyn Future<Output = Result<Value, TransportError>>>>
+///     {
+///         let result = match request["method"].as_str() {
+///             Some("eth_chainId") => json!("0x1"),
+///             Some("eth_blockNumber") => json!("0x64"),
+///             _ => Value::Null,
+///         };
+///
+///         let response = json!({
+///             "jsonrpc": "2.0",
+///             "id": request["id"],
+///             "result": result,
+///         });
+///
+///         Box::pin(async move { Ok(response) })
+///     }
+/// }
+/// ```
+pub trait Transport {
+    /// Send `request` to `endpoint`, and return the response.
+    fn send(
+        &mut self,
+        endpoint: &str,
+        request: Value,
+    ) -> Pin<Box<dyn Future<Output = Result<Value, TransportError>>>>;
+}
+
+/// Call `method` on `endpoint`, returning the `result` of the response.
+pub async fn call(
+    transport: &mut dyn Transport,
+    endpoint: &str,
+    method: &str,
+    params: Value,
+) -> Result<Value, RpcError> {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": method,
+        "params": params,
+    });
+
+    let mut response = transport.send(endpoint, request).await?;
+
+    if let Some(error) = response.get("error") {
+        let code = error["code"]
+            .as_i64()
+            .ok_or_else(|| RpcError::malformed("error code is not an integer"))?;
+        let message = error["message"].as_str().unwrap_or_default().to_owned();
+        return Err(RpcError::Response { code, message });
+    }
+
+    match response.get_mut("result") {
+        Some(result) => Ok(result.take()),
+        None => Err(RpcError::malformed("response has neither result nor error")),
+    }
+}
+
+/// Decode a JSON-RPC quantity (eg. `"0x1"`) into an integer.
+pub fn parse_quantity(value: &Value) -> Result<u64, RpcError> {
+    let text = value
+        .as_str()
+        .ok_or_else(|| RpcError::malformed("quantity is not a string"))?;
+
+    let digits = text
+        .strip_prefix("0x")
+        .ok_or_else(|| RpcError::malformed(format!("quantity `{}` is missing 0x", text)))?;
+
+    u64::from_str_radix(digits, 16)
+        .map_err(|_| RpcError::malformed(format!("quantity `{}` is not hexadecimal", text)))
+}
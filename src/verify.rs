@@ -0,0 +1,253 @@
+//! Verifying that endpoints serve the chains they're listed under.
+//!
+//! A buggy or malicious list can list an endpoint for one chain that actually
+//! serves another (eg. a testnet listed as mainnet.) A [`Verifier`] asks each
+//! endpoint which chain it serves, and reports any disagreement.
+//!
+//! ## Example
+//!
+//! ```
+//! use eip5139::verify::Verifier;
+//! # use eip5139::RpcProviders;
+//! # use eip5139::errors::TransportError;
+//! # use eip5139::transport::Transport;
+//! # use serde_json::Value;
+//! # use std::future::Future;
+//! # use std::pin::Pin;
+//! # struct NoTransport;
+//! # impl Transport for NoTransport {
+//! #     fn send(&mut self, _: &str, _: Value) ->
+//! #         Pin<Box<dyn Future<Output = Result<Value, TransportError>>>>
+//! #     {
+//! #         unimplemented!()
+//! #     }
+//! # }
+//! # let transport = NoTransport;
+//! # let mut list = RpcProviders::default();
+//!
+//! let future = async move {
+//!     let report = Verifier::new(transport).verify(&list).await;
+//!     report.prune(&mut list);
+//! };
+//! ```
+
+use crate::errors::RpcError;
+use crate::secrets::Redacted;
+use crate::transport::{self, Transport};
+use crate::RpcProviders;
+
+use serde_json::json;
+
+use std::fmt;
+
+/// The way an endpoint disagreed with its listing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Disagreement {
+    /// `eth_chainId` returned a different chain id.
+    #[non_exhaustive]
+    ChainId {
+        /// The chain id returned by the endpoint.
+        actual: u64,
+    },
+
+    /// `net_version` returned a different network id.
+    #[non_exhaustive]
+    NetVersion {
+        /// The network id returned by the endpoint.
+        actual: String,
+    },
+}
+
+/// An endpoint that doesn't serve the chain it's listed under.
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct Mismatch {
+    /// Key of the provider listing this endpoint.
+    pub provider: String,
+
+    /// The chain id the endpoint is listed under.
+    pub chain_id: u64,
+
+    /// The address of the endpoint.
+    pub endpoint: String,
+
+    /// What the endpoint reported instead.
+    pub disagreement: Disagreement,
+}
+
+impl fmt::Debug for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mismatch")
+            .field("provider", &self.provider)
+            .field("chain_id", &self.chain_id)
+            .field("endpoint", &Redacted(&self.endpoint))
+            .field("disagreement", &self.disagreement)
+            .finish()
+    }
+}
+
+/// An endpoint that couldn't be verified.
+#[non_exhaustive]
+pub struct Unverified {
+    /// Key of the provider listing this endpoint.
+    pub provider: String,
+
+    /// The chain id the endpoint is listed under.
+    pub chain_id: u64,
+
+    /// The address of the endpoint.
+    pub endpoint: String,
+
+    /// Why verification failed.
+    pub source: RpcError,
+}
+
+impl fmt::Debug for Unverified {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Unverified")
+            .field("provider", &self.provider)
+            .field("chain_id", &self.chain_id)
+            .field("endpoint", &Redacted(&self.endpoint))
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+/// Outcome of verifying every endpoint in a list.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct VerificationReport {
+    /// Endpoints that serve a different chain than listed.
+    pub mismatches: Vec<Mismatch>,
+
+    /// Endpoints that could not be queried.
+    pub unverified: Vec<Unverified>,
+}
+
+impl VerificationReport {
+    /// Whether every reachable endpoint serves the chain it's listed under.
+    pub fn is_consistent(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+
+    /// Remove every mismatched endpoint from `providers`, returning the
+    /// number of endpoints removed.
+    ///
+    /// Chains left without endpoints are removed from their provider.
+    pub fn prune(&self, providers: &mut RpcProviders) -> usize {
+        let mut removed = 0;
+
+        for provider in providers.providers_mut() {
+            for chain in &mut provider.chains {
+                chain.endpoints.retain(|endpoint| {
+                    let mismatched = self.mismatches.iter().any(|m| {
+                        m.provider == provider.key
+                            && m.chain_id == chain.chain_id
+                            && &m.endpoint == endpoint
+                    });
+
+                    if mismatched {
+                        removed += 1;
+                    }
+
+                    !mismatched
+                });
+            }
+
+            provider.chains.retain(|c| !c.endpoints.is_empty());
+        }
+
+        removed
+    }
+}
+
+/// Asks endpoints which chain they serve.
+#[derive(Debug)]
+pub struct Verifier<T> {
+    transport: T,
+    net_version: bool,
+}
+
+impl<T> Verifier<T>
+where
+    T: Transport,
+{
+    /// Create a verifier sending requests through `transport`.
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            net_version: false,
+        }
+    }
+
+    /// Also require `net_version` to match the listed chain id.
+    ///
+    /// Off by default, since some chains (eg. Ethereum Classic) use a network
+    /// id that differs from their chain id.
+    pub fn net_version(mut self, check: bool) -> Self {
+        self.net_version = check;
+        self
+    }
+
+    async fn query(
+        &mut self,
+        chain_id: u64,
+        endpoint: &str,
+    ) -> Result<Option<Disagreement>, RpcError> {
+        let result =
+            transport::call(&mut self.transport, endpoint, "eth_chainId", json!([])).await?;
+        let actual = transport::parse_quantity(&result)?;
+
+        if actual != chain_id {
+            return Ok(Some(Disagreement::ChainId { actual }));
+        }
+
+        if !self.net_version {
+            return Ok(None);
+        }
+
+        let result =
+            transport::call(&mut self.transport, endpoint, "net_version", json!([])).await?;
+        let actual = result
+            .as_str()
+            .ok_or_else(|| RpcError::malformed("network id is not a string"))?;
+
+        if actual == chain_id.to_string() {
+            Ok(None)
+        } else {
+            Ok(Some(Disagreement::NetVersion {
+                actual: actual.to_owned(),
+            }))
+        }
+    }
+
+    /// Query every endpoint in `providers`, one at a time.
+    pub async fn verify(&mut self, providers: &RpcProviders) -> VerificationReport {
+        let mut report = VerificationReport::default();
+
+        for provider in providers.providers() {
+            for chain in &provider.chains {
+                for endpoint in &chain.endpoints {
+                    match self.query(chain.chain_id, endpoint).await {
+                        Ok(None) => (),
+                        Ok(Some(disagreement)) => report.mismatches.push(Mismatch {
+                            provider: provider.key.clone(),
+                            chain_id: chain.chain_id,
+                            endpoint: endpoint.clone(),
+                            disagreement,
+                        }),
+                        Err(source) => report.unverified.push(Unverified {
+                            provider: provider.key.clone(),
+                            chain_id: chain.chain_id,
+                            endpoint: endpoint.clone(),
+                            source,
+                        }),
+                    }
+                }
+            }
+        }
+
+        report
+    }
+}
@@ -1,7 +1,12 @@
+use crate::canonical::Algorithm;
+use crate::chains::{Registry, Snapshot};
 use crate::errors::{FetchError, InnerFetchError};
+use crate::secrets;
 use crate::{fetch, RpcProviders};
 
-use js_sys::{Function, JsString, Promise};
+use js_sys::{Function, JsString, Promise, Reflect};
+
+use serde_json::json;
 
 use std::future::Future;
 use std::pin::Pin;
@@ -35,6 +40,59 @@ export type Version = {
 };
 
 export type Source = {uri: string} | {ens: string};
+
+export type NativeCurrency = {
+    name: string,
+    symbol: string,
+    decimals: number,
+};
+
+export type ChainInfo = {
+    chainId: number,
+    name: string,
+    shortName?: string,
+    nativeCurrency: NativeCurrency,
+    explorers?: {name: string, url: string, standard?: string}[],
+    infoURL?: string,
+    faucets?: string[],
+    slip44?: number,
+    testnet?: boolean,
+};
+
+export type AddEthereumChainParameter = {
+    chainId: string,
+    chainName?: string,
+    nativeCurrency?: NativeCurrency,
+    rpcUrls?: string[],
+    blockExplorerUrls?: string[],
+    iconUrls?: string[],
+};
+
+export type VersionRange = {
+    major: number,
+    minor: number,
+    patch: number,
+    mode?: "^" | "=" | "~" | ">" | ">=" | "<" | "<=",
+    preRelease?: string,
+};
+
+/**
+ * Stable identifier attached to errors thrown by this module as `code`.
+ */
+export type ErrorCode =
+    | "FETCH"
+    | "CYCLE"
+    | "JSON"
+    | "PATCH"
+    | "VALIDATION"
+    | "TOO_DEEP"
+    | "VERSION_MISMATCH"
+    | "ENDPOINT"
+    | "TIMESTAMP_ORDER"
+    | "TIMESTAMP_REGRESSION"
+    | "UNSIGNED"
+    | "BAD_SIGNATURE"
+    | "POLICY";
 "#;
 
 #[wasm_bindgen]
@@ -50,6 +108,12 @@ extern "C" {
 
     #[wasm_bindgen(typescript_type = "Version")]
     pub type Version;
+
+    #[wasm_bindgen(typescript_type = "ChainInfo")]
+    pub type ChainInfo;
+
+    #[wasm_bindgen(typescript_type = "AddEthereumChainParameter")]
+    pub type AddEthereumChainParameter;
 }
 
 #[wasm_bindgen]
@@ -96,6 +160,67 @@ impl RpcProviders {
         Ok(())
     }
 
+    /// The date/time this list was created (in RFC 3339 format.)
+    #[doc(hidden)]
+    #[wasm_bindgen(getter, js_name = timestamp)]
+    pub fn timestamp_js(&self) -> String {
+        self.timestamp.to_string()
+    }
+
+    #[doc(hidden)]
+    #[wasm_bindgen(setter, js_name = timestamp)]
+    pub fn set_timestamp_js(&mut self, timestamp: &str) -> Result<(), JsError> {
+        self.timestamp = timestamp.parse()?;
+        Ok(())
+    }
+
+    /// Hash of the canonical JSON of this list, as a hex string.
+    ///
+    /// `algorithm` is either `"keccak256"` (the default) or `"sha256"`.
+    #[doc(hidden)]
+    #[wasm_bindgen(js_name = contentHash)]
+    pub fn content_hash_js(&self, algorithm: Option<String>) -> Result<String, JsError> {
+        let algorithm = match algorithm.as_deref() {
+            None | Some("keccak256") => Algorithm::Keccak256,
+            Some("sha256") => Algorithm::Sha256,
+            Some(other) => {
+                return Err(JsError::new(&format!("unknown algorithm `{}`", other)));
+            }
+        };
+
+        Ok(self.content_hash(algorithm).to_string())
+    }
+
+    /// The parameter for an EIP-3085 `wallet_addEthereumChain` request adding
+    /// the chain identified by `chainId`, or `undefined` if this list has no
+    /// HTTP(S) endpoints for it.
+    ///
+    /// `info` is the chain's metadata, in chainlist's format. If omitted, the
+    /// metadata bundled with this library is used, if it knows the chain.
+    #[doc(hidden)]
+    #[wasm_bindgen(js_name = addEthereumChainParameter)]
+    pub fn add_ethereum_chain_parameter_js(
+        &self,
+        chain_id: f64,
+        info: Option<ChainInfo>,
+    ) -> Result<Option<AddEthereumChainParameter>, JsError> {
+        // Integers above 2^53 can't be represented exactly.
+        if chain_id < 0.0 || chain_id.fract() != 0.0 || chain_id > 9007199254740991.0 {
+            return Err(JsError::new(&format!("invalid chain id `{}`", chain_id)));
+        }
+        let chain_id = chain_id as u64;
+
+        let info: Option<crate::chains::ChainInfo> = match info {
+            Some(info) => Some(info.into_serde()?),
+            None => Snapshot::bundled().chain(chain_id).cloned(),
+        };
+
+        let parameter =
+            crate::add_chain::AddEthereumChainParameter::new(self, chain_id, info.as_ref());
+
+        Ok(parameter.map(|p| JsValue::from_serde(&p).unwrap().into()))
+    }
+
     /// The providers contained in this list.
     #[doc(hidden)]
     #[wasm_bindgen(getter, js_name = providers)]
@@ -106,7 +231,7 @@ impl RpcProviders {
     #[doc(hidden)]
     #[wasm_bindgen(setter, js_name = providers)]
     pub fn set_providers_js(&mut self, providers: Providers) -> Result<(), JsError> {
-        self.providers = providers.into_serde()?;
+        self.set_providers(providers.into_serde()?);
         Ok(())
     }
 }
@@ -144,12 +269,87 @@ impl From<JsValue> for FetchError {
     }
 }
 
+/// Structured, JSON-compatible details describing `error`.
+fn details(error: &crate::Error) -> serde_json::Value {
+    use crate::Error::*;
+
+    match error {
+        Cycle {
+            duplicate, chain, ..
+        } => json!({
+            "duplicate": duplicate,
+            "chain": chain,
+        }),
+        TooDeep { chain, limit, .. } => json!({
+            "chain": chain,
+            "limit": limit,
+        }),
+        VersionMismatch {
+            child,
+            requested,
+            parent,
+            actual,
+            ..
+        } => json!({
+            "child": child,
+            "requested": requested,
+            "parent": parent,
+            "actual": actual,
+        }),
+        Endpoint { endpoint, .. } => json!({
+            "endpoint": secrets::redact(endpoint),
+        }),
+        TimestampOrder {
+            child,
+            timestamp,
+            parent,
+            parent_timestamp,
+            ..
+        } => json!({
+            "child": child,
+            "timestamp": timestamp,
+            "parent": parent,
+            "parentTimestamp": parent_timestamp,
+        }),
+        TimestampRegression {
+            previous,
+            previous_timestamp,
+            version,
+            timestamp,
+            ..
+        } => json!({
+            "previous": previous,
+            "previousTimestamp": previous_timestamp,
+            "version": version,
+            "timestamp": timestamp,
+        }),
+        Unsigned { list, .. } => json!({
+            "list": list,
+        }),
+        BadSignature { list, source, .. } => json!({
+            "list": list,
+            "reason": source.to_string(),
+        }),
+        Policy { list, source, .. } => json!({
+            "list": list,
+            "reason": source.to_string(),
+        }),
+        _ => json!({}),
+    }
+}
+
 impl From<crate::Error> for JsValue {
     fn from(value: crate::Error) -> Self {
         use crate::Error::Fetch;
 
         let error = js_sys::Error::new(&value.to_string());
 
+        let code = JsValue::from_str(value.code());
+        Reflect::set(&error, &JsValue::from_str("code"), &code).unwrap();
+
+        let details = JsValue::from_serde(&details(&value)).unwrap();
+        Reflect::set(&error, &JsValue::from_str("details"), &details).unwrap();
+
         if let Fetch {
             source: FetchError {
                 inner: InnerFetchError::Js(v),
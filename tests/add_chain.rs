@@ -0,0 +1,134 @@
+pub mod utils;
+
+use eip5139::add_chain::AddEthereumChainParameter;
+use eip5139::chains::{Registry, Snapshot};
+
+use self::utils::fetch_list;
+
+use serde_json::json;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const LIST: &str = r#"{
+  "name": "Root List",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "providers": {
+    "frustrata": {
+      "name": "Frustrata",
+      "logo": "https://frustrata.invalid/logo.svg",
+      "priority": 1,
+      "chains": [
+        {
+          "chainId": 1,
+          "endpoints": [
+            "https://mainnet.frustrata.invalid/",
+            "wss://mainnet.frustrata.invalid/ws"
+          ]
+        },
+        {
+          "chainId": 999999999,
+          "endpoints": ["https://other.frustrata.invalid/"]
+        }
+      ]
+    },
+    "sourceri": {
+      "name": "Sourceri",
+      "logo": "https://sourceri.invalid/logo.png",
+      "priority": 0,
+      "chains": [
+        {
+          "chainId": 1,
+          "endpoints": [
+            "https://mainnet.sourceri.invalid/",
+            "https://mainnet.frustrata.invalid/"
+          ]
+        },
+        {
+          "chainId": 5,
+          "endpoints": ["wss://goerli.sourceri.invalid/"]
+        }
+      ]
+    },
+    "floop": {
+      "name": "Floop",
+      "chains": [
+        {
+          "chainId": 1,
+          "endpoints": ["https://mainnet.floop.invalid/"]
+        }
+      ]
+    }
+  }
+}"#;
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn with_metadata() {
+    let list = fetch_list(LIST);
+    let info = Snapshot::bundled().chain(1);
+
+    let parameter = AddEthereumChainParameter::new(&list, 1, info).unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&parameter).unwrap(),
+        json!({
+            "chainId": "0x1",
+            "chainName": "Ethereum Mainnet",
+            "nativeCurrency": {
+                "name": "Ether",
+                "symbol": "ETH",
+                "decimals": 18,
+            },
+            "rpcUrls": [
+                "https://mainnet.sourceri.invalid/",
+                "https://mainnet.frustrata.invalid/",
+                "https://mainnet.floop.invalid/",
+            ],
+            "blockExplorerUrls": [
+                "https://etherscan.io",
+                "https://eth.blockscout.com",
+                "https://ethereum.dex.guru",
+                "https://ethereum.routescan.io",
+            ],
+            "iconUrls": [
+                "https://sourceri.invalid/logo.png",
+                "https://frustrata.invalid/logo.svg",
+            ],
+        })
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn without_metadata() {
+    let list = fetch_list(LIST);
+
+    let parameter = AddEthereumChainParameter::new(&list, 999999999, None).unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&parameter).unwrap(),
+        json!({
+            "chainId": "0x3b9ac9ff",
+            "rpcUrls": ["https://other.frustrata.invalid/"],
+            "iconUrls": ["https://frustrata.invalid/logo.svg"],
+        })
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn no_http_endpoints() {
+    let list = fetch_list(LIST);
+
+    // Only a WebSocket endpoint.
+    assert!(AddEthereumChainParameter::new(&list, 5, None).is_none());
+
+    // Not in the list at all.
+    assert!(AddEthereumChainParameter::new(&list, 10, Snapshot::bundled().chain(10)).is_none());
+}
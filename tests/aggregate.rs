@@ -0,0 +1,154 @@
+pub mod utils;
+
+use eip5139::aggregate::{Aggregator, ConflictPolicy};
+use eip5139::{RpcProviders, Source};
+
+use futures_executor::LocalPool;
+
+use self::utils::Fetch;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const COMMUNITY: &str = r#"{
+  "name": "Community",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "providers": {
+    "shared": {
+      "name": "Shared Community",
+      "priority": 5,
+      "chains": [
+        {
+          "chainId": 1,
+          "endpoints": ["https://mainnet.shared.invalid/"]
+        }
+      ]
+    },
+    "community": {
+      "name": "Community Node",
+      "chains": [
+        {
+          "chainId": 1,
+          "endpoints": ["https://mainnet.community.invalid/"]
+        }
+      ]
+    }
+  }
+}"#;
+
+const VENDOR: &str = r#"{
+  "name": "Vendor",
+  "version": {
+    "major": 3,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2005-08-08T00:00:00.0Z",
+  "providers": {
+    "shared": {
+      "name": "Shared Vendor",
+      "priority": 1,
+      "chains": [
+        {
+          "chainId": 1,
+          "endpoints": [
+            "https://mainnet.shared.invalid/",
+            "https://mainnet2.shared.invalid/"
+          ]
+        },
+        {
+          "chainId": 10,
+          "endpoints": ["https://optimism.shared.invalid/"]
+        }
+      ]
+    }
+  }
+}"#;
+
+fn lists() -> (RpcProviders, RpcProviders) {
+    let mut pool = LocalPool::new();
+
+    let fetch = Fetch::with_two(COMMUNITY, VENDOR);
+    let community = pool
+        .run_until(RpcProviders::fetch(fetch, Source::Uri("file://one".into())))
+        .unwrap();
+
+    let fetch = Fetch::with_two(COMMUNITY, VENDOR);
+    let vendor = pool
+        .run_until(RpcProviders::fetch(fetch, Source::Uri("file://two".into())))
+        .unwrap();
+
+    (community, vendor)
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn prefer_first() {
+    let (community, vendor) = lists();
+
+    let merged = Aggregator::new(ConflictPolicy::PreferFirst)
+        .list(&community)
+        .list(&vendor)
+        .aggregate();
+
+    assert_eq!(merged.providers().len(), 2);
+
+    let shared = &merged.providers()[0];
+    assert_eq!(shared.provider.name, "Shared Community");
+    assert_eq!(shared.provider.chains.len(), 1);
+
+    let origins: Vec<_> = shared.origins.iter().map(|o| o.name.as_str()).collect();
+    assert_eq!(origins, ["Community", "Vendor"]);
+
+    let origins = merged.origins("community").unwrap();
+    assert_eq!(origins.len(), 1);
+    assert_eq!(origins[0].index, 0);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn prefer_highest_priority() {
+    let (community, vendor) = lists();
+
+    let merged = Aggregator::new(ConflictPolicy::PreferHighestPriority)
+        .list(&community)
+        .list(&vendor)
+        .aggregate();
+
+    let shared = &merged.providers()[0];
+    assert_eq!(shared.provider.name, "Shared Vendor");
+    assert_eq!(shared.origins[0].name, "Vendor");
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn union_endpoints() {
+    let (community, vendor) = lists();
+
+    let merged = Aggregator::new(ConflictPolicy::UnionEndpoints)
+        .list(&community)
+        .list(&vendor)
+        .aggregate()
+        .into_list();
+
+    assert_eq!(merged.name, "Community");
+
+    let shared = &merged.providers()[0];
+    assert_eq!(shared.name, "Shared Community");
+    assert_eq!(shared.priority, Some(1));
+    assert_eq!(
+        shared.chain(1).unwrap().endpoints,
+        [
+            "https://mainnet.shared.invalid/",
+            "https://mainnet2.shared.invalid/"
+        ]
+    );
+
+    assert_eq!(merged.chain_ids().collect::<Vec<_>>(), [1, 10]);
+    assert_eq!(merged.endpoints_for(1).count(), 3);
+}
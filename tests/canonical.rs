@@ -0,0 +1,185 @@
+pub mod utils;
+
+use eip5139::canonical::{self, Algorithm};
+use eip5139::{Provider, RpcProviders, Source};
+
+use futures_executor::LocalPool;
+
+use serde_json::{json, Value};
+
+use self::utils::Fetch;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const LIST: &str = r#"{
+  "name": "Root List",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "providers": {
+    "frustrata": {
+      "name": "Frustrata",
+      "chains": [{ "chainId": 1, "endpoints": ["https://frustrata.invalid/"] }]
+    },
+    "sourceri": {
+      "name": "Sourceri",
+      "priority": 0,
+      "chains": [{ "chainId": 1, "endpoints": ["https://sourceri.invalid/"] }]
+    }
+  }
+}"#;
+
+const SHUFFLED: &str = r#"{"providers":{"sourceri":{"chains":[{"endpoints":["https://sourceri.invalid/"],"chainId":1}],"priority":0,"name":"Sourceri"},"frustrata":{"chains":[{"endpoints":["https://frustrata.invalid/"],"chainId":1}],"name":"Frustrata"}},"timestamp":"2004-08-08T00:00:00.0Z","version":{"patch":0,"minor":0,"major":1},"name":"Root List"}"#;
+
+fn resolve(contents: &str) -> RpcProviders {
+    let fetch = Fetch::with_one(contents);
+    let mut pool = LocalPool::new();
+    pool.run_until(RpcProviders::fetch(fetch, Source::Uri("file://one".into())))
+        .unwrap()
+}
+
+fn number(text: &str) -> String {
+    let value: Value = serde_json::from_str(text).unwrap();
+    canonical::to_string(&value)
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn numbers() {
+    assert_eq!(number("0"), "0");
+    assert_eq!(number("-0.0"), "0");
+    assert_eq!(number("1.0"), "1");
+    assert_eq!(number("1e0"), "1");
+    assert_eq!(number("-42"), "-42");
+    assert_eq!(number("123.456"), "123.456");
+    assert_eq!(number("0.000001"), "0.000001");
+    assert_eq!(number("1e-7"), "1e-7");
+    assert_eq!(number("1e21"), "1e+21");
+    assert_eq!(number("1e20"), "100000000000000000000");
+    assert_eq!(number("-1.5e300"), "-1.5e+300");
+    assert_eq!(number("9007199254740993"), "9007199254740992");
+    assert_eq!(number("18446744073709551615"), "18446744073709552000");
+    assert_eq!(number("-9223372036854775808"), "-9223372036854776000");
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn sorted_by_utf16() {
+    // U+FF61 sorts before U+1F600 in UTF-8, but after it in UTF-16.
+    let value = json!({ "\u{ff61}": 1, "\u{1f600}": 2, "b": [], "a": { "y": null, "x": "\n" } });
+
+    assert_eq!(
+        canonical::to_string(&value),
+        "{\"a\":{\"x\":\"\\n\",\"y\":null},\"b\":[],\"\u{1f600}\":2,\"\u{ff61}\":1}"
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn hashes() {
+    let empty = json!({});
+
+    let sha256 = canonical::hash(&empty, Algorithm::Sha256);
+    assert_eq!(sha256.algorithm(), Algorithm::Sha256);
+    assert_eq!(
+        sha256.to_string(),
+        "0x44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a"
+    );
+
+    let keccak256 = canonical::hash(&empty, Algorithm::Keccak256);
+    assert_ne!(keccak256, sha256);
+    assert_eq!(
+        serde_json::to_value(keccak256).unwrap(),
+        json!(keccak256.to_string())
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn fractional_integer() {
+    let fetch = Fetch::with_one(LIST.replace(r#""chainId": 1,"#, r#""chainId": 1.0,"#));
+    let mut pool = LocalPool::new();
+    let err = pool
+        .run_until(RpcProviders::fetch(fetch, Source::Uri("file://one".into())))
+        .unwrap_err();
+
+    assert_eq!(err.code(), "JSON");
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn resolved_hash_ignores_order() {
+    let list = resolve(LIST);
+    let shuffled = resolve(SHUFFLED);
+
+    for algorithm in [Algorithm::Keccak256, Algorithm::Sha256] {
+        assert_eq!(
+            list.content_hash(algorithm),
+            shuffled.content_hash(algorithm)
+        );
+    }
+
+    let document: Value = serde_json::from_str(LIST).unwrap();
+    assert_eq!(
+        canonical::to_string(&list.to_json()),
+        canonical::to_string(&document)
+    );
+
+    let mut changed = resolve(LIST);
+    changed.name = "Changed".into();
+    assert_ne!(
+        list.content_hash(Algorithm::Keccak256),
+        changed.content_hash(Algorithm::Keccak256)
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn keyless_providers_kept() {
+    let keyless = |name: &str| Provider::new("", name);
+
+    let mut one = resolve(LIST);
+    one.set_providers(vec![keyless("Frustrata")]);
+
+    let mut two = resolve(LIST);
+    two.set_providers(vec![keyless("Sourceri"), keyless("Frustrata")]);
+
+    let mut reversed = resolve(LIST);
+    reversed.set_providers(vec![keyless("Frustrata"), keyless("Sourceri")]);
+
+    let json = two.to_json();
+    let providers = json["providers"].as_object().unwrap();
+    assert_eq!(providers.len(), 2);
+    assert!(providers.keys().all(|k| k.starts_with("provider-")));
+
+    assert_ne!(
+        one.content_hash(Algorithm::Keccak256),
+        two.content_hash(Algorithm::Keccak256)
+    );
+    assert_eq!(
+        two.content_hash(Algorithm::Keccak256),
+        reversed.content_hash(Algorithm::Keccak256)
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn duplicate_keys_kept() {
+    let mut list = resolve(LIST);
+    list.set_providers(vec![
+        Provider::new("same", "Frustrata"),
+        Provider::new("same", "Sourceri"),
+        Provider::new("same", "Sourceri"),
+        Provider::new("other", "Other"),
+    ]);
+
+    let json = list.to_json();
+    let providers = json["providers"].as_object().unwrap();
+    assert_eq!(providers.len(), 4);
+    assert_eq!(providers["other"]["name"], "Other");
+    assert!(!providers.contains_key("same"));
+}
@@ -0,0 +1,203 @@
+pub mod utils;
+
+use eip5139::chains::{self, ChainInfo, Registry, Snapshot};
+
+use self::utils::fetch_list;
+
+use std::collections::HashMap;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const LIST: &str = r#"{
+  "name": "Root List",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "providers": {
+    "frustrata": {
+      "name": "Frustrata",
+      "chains": [
+        {
+          "chainId": 1,
+          "endpoints": ["https://mainnet.frustrata.invalid/"]
+        },
+        {
+          "chainId": 11155111,
+          "endpoints": ["https://sepolia.frustrata.invalid/"]
+        }
+      ]
+    },
+    "sourceri": {
+      "name": "Sourceri",
+      "chains": [
+        {
+          "chainId": 137,
+          "endpoints": ["https://polygon.sourceri.invalid/"]
+        },
+        {
+          "chainId": 999999999,
+          "endpoints": ["https://unknown.sourceri.invalid/"]
+        }
+      ]
+    }
+  }
+}"#;
+
+const CUSTOM: &str = r#"[
+  {
+    "name": "Frustrata Chain",
+    "chain": "FRU",
+    "chainId": 999999999,
+    "shortName": "fru",
+    "rpc": ["https://unknown.sourceri.invalid/"],
+    "nativeCurrency": { "name": "Frustration", "symbol": "FRU", "decimals": 9 },
+    "slip44": 60
+  }
+]"#;
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn bundled() {
+    let registry = Snapshot::bundled();
+
+    let mainnet = registry.chain(1).unwrap();
+    assert_eq!(mainnet.name, "Ethereum Mainnet");
+    assert_eq!(mainnet.short_name, "eth");
+    assert_eq!(mainnet.native_currency.symbol, "ETH");
+    assert_eq!(mainnet.native_currency.decimals, 18);
+    assert_eq!(mainnet.explorers[0].url, "https://etherscan.io");
+    assert!(!mainnet.is_testnet());
+
+    assert!(registry.chain(11155111).unwrap().is_testnet());
+    assert!(registry.chain(84532).unwrap().is_testnet());
+
+    // Mainnets may have faucets too.
+    let gnosis = registry.chain(100).unwrap();
+    assert!(!gnosis.faucets.is_empty());
+    assert!(!gnosis.is_testnet());
+    assert!(registry.chain(999999999).is_none());
+
+    let ids: Vec<_> = registry.iter().map(|c| c.chain_id).collect();
+    let mut sorted = ids.clone();
+    sorted.sort_unstable();
+    assert_eq!(ids, sorted);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn custom_snapshot() {
+    let snapshot = Snapshot::from_json(CUSTOM).unwrap();
+
+    let info = snapshot.chain(999999999).unwrap();
+    assert_eq!(info.name, "Frustrata Chain");
+    assert_eq!(info.native_currency.decimals, 9);
+    assert!(info.explorers.is_empty());
+    assert!(info.info_url.is_none());
+    assert!(!info.is_testnet());
+
+    assert!(Snapshot::from_json("{}").is_err());
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn testnet_override() {
+    let json = CUSTOM.replace(r#""slip44": 60"#, r#""slip44": 60, "testnet": true"#);
+    let snapshot = Snapshot::from_json(&json).unwrap();
+    assert!(snapshot.chain(999999999).unwrap().is_testnet());
+
+    let json = CUSTOM.replace(r#""slip44": 60"#, r#""slip44": 1, "testnet": false"#);
+    let snapshot = Snapshot::from_json(&json).unwrap();
+    assert!(!snapshot.chain(999999999).unwrap().is_testnet());
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn join() {
+    let list = fetch_list(LIST);
+    let joined = chains::join(&list, Snapshot::bundled());
+
+    let names: Vec<_> = joined
+        .iter()
+        .map(|c| (c.chain_id, c.info.map(|i| i.name.as_str())))
+        .collect();
+
+    assert_eq!(
+        names,
+        [
+            (1, Some("Ethereum Mainnet")),
+            (137, Some("Polygon Mainnet")),
+            (11155111, Some("Sepolia")),
+            (999999999, None),
+        ]
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn user_registry() {
+    let list = fetch_list(LIST);
+
+    let mut registry: HashMap<u64, ChainInfo> = HashMap::new();
+    let snapshot = Snapshot::from_json(CUSTOM).unwrap();
+    for info in snapshot.iter() {
+        registry.insert(info.chain_id, info.clone());
+    }
+
+    let known: Vec<_> = chains::join(&list, &registry)
+        .into_iter()
+        .filter(|c| c.info.is_some())
+        .map(|c| c.chain_id)
+        .collect();
+
+    assert_eq!(known, [999999999]);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn mainnets_only() {
+    let mut list = fetch_list(LIST);
+    let mut providers = list.providers().to_vec();
+    let mut gnosis = providers[0].chains[0].clone();
+    gnosis.chain_id = 100;
+    providers[0].chains.push(gnosis);
+    list.set_providers(providers);
+
+    chains::mainnets_only(&mut list, Snapshot::bundled());
+
+    assert_eq!(list.chain_ids().collect::<Vec<_>>(), [1, 100, 137]);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn testnets_only() {
+    let mut list = fetch_list(LIST);
+    chains::testnets_only(&mut list, Snapshot::bundled());
+
+    assert_eq!(list.chain_ids().collect::<Vec<_>>(), [11155111]);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn retain() {
+    let mut list = fetch_list(LIST);
+
+    let mut registry: Snapshot = Snapshot::bundled().iter().cloned().collect();
+    registry.insert(
+        Snapshot::from_json(CUSTOM)
+            .unwrap()
+            .chain(999999999)
+            .unwrap()
+            .clone(),
+    );
+
+    chains::retain(&mut list, &registry, |_, info| {
+        info.map(|i| i.native_currency.symbol != "ETH")
+            .unwrap_or(false)
+    });
+
+    assert_eq!(list.chain_ids().collect::<Vec<_>>(), [137, 999999999]);
+}
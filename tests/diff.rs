@@ -0,0 +1,147 @@
+pub mod utils;
+
+use eip5139::diff::{Bump, Diff};
+use eip5139::{RpcProviders, Source};
+
+use futures_executor::LocalPool;
+
+use serde_json::json;
+
+use self::utils::Fetch;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const BEFORE: &str = r#"{
+  "name": "Root List",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "providers": {
+    "frustrata": {
+      "name": "Frustrata",
+      "priority": 1,
+      "chains": [
+        {
+          "chainId": 1,
+          "endpoints": ["https://one.frustrata.invalid/"]
+        },
+        {
+          "chainId": 5,
+          "endpoints": ["https://goerli.frustrata.invalid/"]
+        }
+      ]
+    },
+    "sourceri": {
+      "name": "Sourceri",
+      "chains": []
+    }
+  }
+}"#;
+
+const AFTER: &str = r#"{
+  "name": "Renamed List",
+  "logo": "https://example.invalid/logo.png",
+  "version": {
+    "major": 1,
+    "minor": 1,
+    "patch": 0
+  },
+  "timestamp": "2005-08-08T00:00:00.0Z",
+  "providers": {
+    "frustrata": {
+      "name": "Frustrata Inc.",
+      "priority": 2,
+      "chains": [
+        {
+          "chainId": 1,
+          "endpoints": ["https://two.frustrata.invalid/"]
+        },
+        {
+          "chainId": 10,
+          "endpoints": ["https://optimism.frustrata.invalid/"]
+        }
+      ]
+    },
+    "wagmi": {
+      "name": "Wagmi",
+      "chains": []
+    }
+  }
+}"#;
+
+fn resolve(contents: &str) -> RpcProviders {
+    let fetch = Fetch::with_one(contents);
+    let mut pool = LocalPool::new();
+    pool.run_until(RpcProviders::fetch(fetch, Source::Uri("file://one".into())))
+        .unwrap()
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn human_readable() {
+    let diff = Diff::between(&resolve(BEFORE), &resolve(AFTER));
+
+    assert_eq!(diff.bump(), Bump::Minor);
+    assert_eq!(
+        diff.to_string(),
+        r#"~ name: "Root List" -> "Renamed List"
+~ logo: none -> https://example.invalid/logo.png
+~ version: 1.0.0 -> 1.1.0 (minor)
+~ timestamp: 2004-08-08T00:00:00.0Z -> 2005-08-08T00:00:00.0Z
+~ provider frustrata: name "Frustrata" -> "Frustrata Inc."
+~ provider frustrata: priority 1 -> 2
+- provider frustrata: chain 1: https://one.frustrata.invalid/
++ provider frustrata: chain 1: https://two.frustrata.invalid/
+- provider frustrata: chain 5
++ provider frustrata: chain 10
+- provider sourceri
++ provider wagmi
+"#
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn json() {
+    let diff = Diff::between(&resolve(BEFORE), &resolve(AFTER));
+    let json = diff.to_json();
+
+    assert_eq!(json.as_array().unwrap().len(), diff.entries.len());
+    assert_eq!(
+        json[2],
+        json!({
+            "type": "version",
+            "previous": { "major": 1, "minor": 0, "patch": 0 },
+            "current": { "major": 1, "minor": 1, "patch": 0 },
+            "bump": "minor",
+        })
+    );
+    assert_eq!(
+        json[7],
+        json!({
+            "type": "endpointAdded",
+            "provider": "frustrata",
+            "chainId": 1,
+            "endpoint": "https://two.frustrata.invalid/",
+        })
+    );
+    assert_eq!(
+        json[11],
+        json!({ "type": "providerAdded", "provider": "wagmi" })
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn identical() {
+    let diff = Diff::between(&resolve(BEFORE), &resolve(BEFORE));
+
+    assert!(diff.is_empty());
+    assert_eq!(diff.bump(), Bump::None);
+    assert_eq!(diff.to_string(), "");
+    assert_eq!(diff.to_json(), json!([]));
+}
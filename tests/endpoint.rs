@@ -0,0 +1,100 @@
+pub mod utils;
+
+use eip5139::endpoint::{Scheme, Transport};
+use eip5139::errors::*;
+use eip5139::{RpcProviders, Source};
+
+use futures_executor::LocalPool;
+
+use self::utils::Fetch;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn list(endpoints: &str) -> String {
+    format!(
+        r#"{{
+  "name": "Root List",
+  "version": {{
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  }},
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "providers": {{
+    "some-key": {{
+      "name": "Frustrata",
+      "chains": [
+        {{
+          "chainId": 1,
+          "endpoints": {}
+        }}
+      ]
+    }}
+  }}
+}}"#,
+        endpoints
+    )
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn classify_transports() {
+    let one = list(
+        r#"[
+            "https://mainnet.frustrata.invalid/",
+            "wss://mainnet.frustrata.invalid/ws",
+            "ipc:///var/run/geth.ipc"
+        ]"#,
+    );
+
+    let fetch = Fetch::with_one(one);
+    let mut pool = LocalPool::new();
+    let list = pool
+        .run_until(RpcProviders::fetch(fetch, Source::Uri("file://one".into())))
+        .unwrap();
+
+    let chain = &list.providers()[0].chains[0];
+
+    let ws: Vec<_> = chain.websocket_endpoints().collect();
+    assert_eq!(ws.len(), 1);
+    assert_eq!(ws[0].scheme(), Scheme::Wss);
+    assert_eq!(ws[0].host(), Some("mainnet.frustrata.invalid"));
+    assert_eq!(ws[0].port(), Some(443));
+    assert_eq!(ws[0].path(), "/ws");
+    assert!(ws[0].is_secure());
+
+    let http: Vec<_> = chain.http_endpoints().collect();
+    assert_eq!(http.len(), 1);
+    assert_eq!(http[0].as_str(), "https://mainnet.frustrata.invalid/");
+
+    let ipc: Vec<_> = chain.endpoints_with(Transport::Ipc).collect();
+    assert_eq!(ipc.len(), 1);
+    assert_eq!(ipc[0].path(), "/var/run/geth.ipc");
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn reject_non_uri() {
+    let one = list(r#"["https://mainnet.frustrata.invalid/", "not a url"]"#);
+
+    let fetch = Fetch::with_one(one);
+    let mut pool = LocalPool::new();
+    let err = pool
+        .run_until(RpcProviders::fetch(fetch, Source::Uri("file://one".into())))
+        .unwrap_err();
+
+    // With format assertions, the endpoint is rejected during validation.
+    // Without, it's rejected when parsed.
+    match err {
+        #[cfg(not(feature = "formats"))]
+        Error::Endpoint { endpoint, .. } => assert_eq!(endpoint, "not a url"),
+        #[cfg(feature = "formats")]
+        Error::Validation { source, .. } => {
+            let message = source.to_string();
+            assert!(message.contains("/providers/some-key/chains/0/endpoints/1"));
+            assert!(message.contains("not a url"));
+        }
+        other => panic!("expected Endpoint or Validation, but got: {:?}", other),
+    }
+}
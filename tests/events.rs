@@ -0,0 +1,103 @@
+pub mod utils;
+
+use eip5139::events::Event;
+use eip5139::{Resolver, Source};
+
+use futures_executor::LocalPool;
+
+use self::utils::Fetch;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn extension_events_in_order() {
+    let one = r#"{
+  "name": "Root List",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "providers": {
+  }
+}"#;
+
+    let two = r#"{
+  "name": "Extension List",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2024-08-08T00:00:00.0Z",
+  "extends": {
+    "uri": "file://one",
+    "version": {
+      "major": 1,
+      "minor": 0,
+      "patch": 0
+    }
+  },
+  "changes": [
+    {
+        "op": "add",
+        "path": "/some-key",
+        "value": {
+            "name": "Frustrata",
+            "chains": [
+                {
+                    "chainId": 1,
+                    "endpoints": [
+                        "https://mainnet1.frustrata.invalid/"
+                    ]
+                }
+            ]
+        }
+    }
+  ]
+}"#;
+
+    let one_source = Source::Uri("file://one".into());
+    let two_source = Source::Uri("file://two".into());
+
+    let mut events = Vec::new();
+    let fetch = Fetch::with_two(one, two);
+    let resolver = Resolver::new(fetch).observer(|event: &Event<'_>| {
+        let name = match event {
+            Event::FetchStarted { .. } => "fetch-started",
+            Event::FetchFinished { .. } => "fetch-finished",
+            Event::Validated { .. } => "validated",
+            Event::VersionChecked { parent, .. } => {
+                assert_eq!(**parent, Source::Uri("file://one".into()));
+                "version-checked"
+            }
+            Event::PatchApplied { .. } => "patch-applied",
+            other => panic!("unexpected event: {:?}", other),
+        };
+        events.push((name, event.source().clone()));
+    });
+
+    let mut pool = LocalPool::new();
+    let list = pool
+        .run_until(resolver.resolve(two_source.clone()))
+        .unwrap();
+    assert_eq!(list.providers().len(), 1);
+
+    assert_eq!(
+        events,
+        [
+            ("fetch-started", two_source.clone()),
+            ("fetch-finished", two_source.clone()),
+            ("validated", two_source.clone()),
+            ("fetch-started", one_source.clone()),
+            ("fetch-finished", one_source.clone()),
+            ("validated", one_source),
+            ("version-checked", two_source.clone()),
+            ("patch-applied", two_source.clone()),
+            ("validated", two_source),
+        ]
+    );
+}
@@ -5,7 +5,7 @@ use eip5139::{RpcProviders, Source};
 
 use futures_executor::LocalPool;
 
-use self::utils::Fetch;
+use self::utils::{Fetch, SharedFetch};
 
 #[cfg(target_family = "wasm")]
 use wasm_bindgen_test::wasm_bindgen_test;
@@ -191,8 +191,227 @@ fn invalid_version() {
         .run_until(RpcProviders::fetch(fetch, Source::Uri("file://two".into())))
         .unwrap_err();
 
+    assert_eq!(err.code(), "VERSION_MISMATCH");
+
     match err {
-        Error::VersionMismatch { .. } => (),
+        Error::VersionMismatch {
+            child,
+            requested,
+            parent,
+            actual,
+            ..
+        } => {
+            assert_eq!(child, Source::Uri("file://two".into()));
+            assert_eq!(parent, Source::Uri("file://one".into()));
+            assert_eq!(requested.to_string(), "^0.2.0");
+            assert_eq!(actual.to_string(), "0.1.1+XPSr.p.I.g.l");
+        }
         other => panic!("expected VersionMismatch, but got: {:?}", other),
     }
 }
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn cycle() {
+    let one = r#"{
+  "name": "First List",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "extends": {
+    "uri": "file://two",
+    "version": {
+      "major": 1,
+      "minor": 0,
+      "patch": 0
+    }
+  },
+  "changes": []
+}"#;
+
+    let two = r#"{
+  "name": "Second List",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "extends": {
+    "uri": "file://one",
+    "version": {
+      "major": 1,
+      "minor": 0,
+      "patch": 0
+    }
+  },
+  "changes": []
+}"#;
+
+    let fetch = Fetch::with_two(one, two);
+    let mut pool = LocalPool::new();
+    let err = pool
+        .run_until(RpcProviders::fetch(fetch, Source::Uri("file://two".into())))
+        .unwrap_err();
+
+    assert_eq!(err.code(), "CYCLE");
+
+    match err {
+        Error::Cycle {
+            duplicate, chain, ..
+        } => {
+            assert_eq!(duplicate, Source::Uri("file://two".into()));
+            assert_eq!(
+                chain,
+                [
+                    Source::Uri("file://two".into()),
+                    Source::Uri("file://one".into())
+                ]
+            );
+        }
+        other => panic!("expected Cycle, but got: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn valid_three_levels() {
+    let one = r#"{
+  "name": "Root List",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "providers": {
+  }
+}"#;
+
+    let two = r#"{
+  "name": "Extension List",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "extends": {
+    "uri": "file://one",
+    "version": {
+      "major": 1,
+      "minor": 0,
+      "patch": 0
+    }
+  },
+  "changes": [
+    {
+        "op": "add",
+        "path": "/some-key",
+        "value": {
+            "name": "Frustrata",
+            "chains": [
+                {
+                    "chainId": 1,
+                    "endpoints": [
+                        "https://mainnet1.frustrata.invalid/"
+                    ]
+                }
+            ]
+        }
+    }
+  ]
+}"#;
+
+    let three = r#"{
+  "name": "Extension Extension List",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "extends": {
+    "uri": "file://two",
+    "version": {
+      "major": 1,
+      "minor": 0,
+      "patch": 0
+    }
+  },
+  "changes": [
+    {
+        "op": "replace",
+        "path": "/some-key/name",
+        "value": "Frustrated"
+    }
+  ]
+}"#;
+
+    let fetch = Fetch::with_three(one, two, three);
+    let mut pool = LocalPool::new();
+    let list = pool
+        .run_until(RpcProviders::fetch(
+            fetch,
+            Source::Uri("file://three".into()),
+        ))
+        .unwrap();
+
+    assert_eq!(list.name, "Extension Extension List");
+
+    let providers = list.providers();
+    assert_eq!(providers.len(), 1);
+    assert_eq!(providers[0].name, "Frustrated");
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn too_deep() {
+    let fetch = SharedFetch::default();
+    let uri = |idx: usize| format!("file://list{}", idx);
+
+    for idx in 0..=11 {
+        let list = format!(
+            r#"{{
+  "name": "List {}",
+  "version": {{
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  }},
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "extends": {{
+    "uri": "{}",
+    "version": {{
+      "major": 1,
+      "minor": 0,
+      "patch": 0
+    }}
+  }},
+  "changes": []
+}}"#,
+            idx,
+            uri(idx + 1)
+        );
+        fetch.set(uri(idx), list);
+    }
+
+    let mut pool = LocalPool::new();
+    let err = pool
+        .run_until(RpcProviders::fetch(fetch, Source::Uri(uri(0))))
+        .unwrap_err();
+
+    assert_eq!(err.code(), "TOO_DEEP");
+
+    match err {
+        Error::TooDeep { chain, limit, .. } => {
+            let expected: Vec<_> = (0..=11).map(|idx| Source::Uri(uri(idx))).collect();
+            assert_eq!(chain, expected);
+            assert_eq!(limit, 10);
+        }
+        other => panic!("expected TooDeep, but got: {:?}", other),
+    }
+}
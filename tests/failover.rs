@@ -0,0 +1,239 @@
+pub mod utils;
+
+use eip5139::errors::{FailoverError, RpcError};
+use eip5139::failover::{BreakerState, FailoverClient};
+
+use futures_executor::LocalPool;
+
+use serde_json::json;
+
+use std::time::Duration;
+
+use self::utils::{fetch_list, MockRpc, Node};
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const LIST: &str = r#"{
+  "name": "Root List",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "providers": {
+    "first": {
+      "name": "First",
+      "priority": 0,
+      "chains": [
+        {
+          "chainId": 1,
+          "endpoints": ["https://first.invalid/"]
+        }
+      ]
+    },
+    "second": {
+      "name": "Second",
+      "priority": 1,
+      "chains": [
+        {
+          "chainId": 1,
+          "endpoints": ["https://second.invalid/"]
+        }
+      ]
+    }
+  }
+}"#;
+
+const UP: Node = Node::Up {
+    chain_id: 1,
+    block: 10,
+};
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn fails_over_in_priority_order() {
+    let mut pool = LocalPool::new();
+    let list = fetch_list(LIST);
+
+    let rpc = MockRpc::with_nodes([
+        ("https://first.invalid/", Node::Down),
+        ("https://second.invalid/", UP),
+    ]);
+
+    let mut client = FailoverClient::new(rpc.clone(), &list, 1);
+    let result = pool
+        .run_until(client.request("eth_blockNumber", json!([])))
+        .unwrap();
+    assert_eq!(result, json!("0xa"));
+
+    let endpoints: Vec<_> = rpc.requests().into_iter().map(|(e, _)| e).collect();
+    assert_eq!(
+        endpoints,
+        ["https://first.invalid/", "https://second.invalid/"]
+    );
+
+    let stats: Vec<_> = client.stats().collect();
+    assert_eq!(stats[0].provider, "first");
+    assert_eq!(stats[0].failures, 1);
+    assert_eq!(stats[1].successes, 1);
+    assert!(stats[1].last_latency.is_some());
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn non_retryable_error_is_returned() {
+    let mut pool = LocalPool::new();
+    let list = fetch_list(LIST);
+
+    let rpc = MockRpc::with_nodes([
+        ("https://first.invalid/", Node::Error { code: 3 }),
+        ("https://second.invalid/", UP),
+    ]);
+
+    let mut client = FailoverClient::new(rpc.clone(), &list, 1);
+    let err = pool
+        .run_until(client.request("eth_call", json!([])))
+        .unwrap_err();
+
+    match err {
+        FailoverError::Rpc { endpoint, .. } => assert_eq!(endpoint, "https://first.invalid/"),
+        other => panic!("expected Rpc, got {:?}", other),
+    }
+
+    assert_eq!(rpc.requests().len(), 1);
+
+    let mut client = FailoverClient::new(rpc, &list, 5);
+    let err = pool
+        .run_until(client.request("eth_call", json!([])))
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        FailoverError::NoEndpoints { chain_id: 5, .. }
+    ));
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn circuit_breaker() {
+    let mut pool = LocalPool::new();
+    let list = fetch_list(LIST);
+
+    let rpc = MockRpc::with_nodes([
+        ("https://first.invalid/", Node::Error { code: -32005 }),
+        ("https://second.invalid/", UP),
+    ]);
+
+    let mut client = FailoverClient::new(rpc.clone(), &list, 1)
+        .failure_threshold(2)
+        .cooldown(Duration::from_secs(3600));
+
+    for _ in 0..3 {
+        pool.run_until(client.request("eth_blockNumber", json!([])))
+            .unwrap();
+    }
+
+    // The first endpoint is skipped once its breaker opens.
+    let first = rpc
+        .requests()
+        .iter()
+        .filter(|(e, _)| e == "https://first.invalid/")
+        .count();
+    assert_eq!(first, 2);
+
+    let stats: Vec<_> = client.stats().cloned().collect();
+    assert_eq!(stats[0].state, BreakerState::Open);
+    assert_eq!(stats[1].state, BreakerState::Closed);
+    assert_eq!(stats[1].successes, 3);
+
+    // Once the cooldown has passed, a successful trial closes the breaker.
+    let mut client = FailoverClient::new(rpc.clone(), &list, 1)
+        .failure_threshold(1)
+        .cooldown(Duration::ZERO);
+
+    pool.run_until(client.request("eth_blockNumber", json!([])))
+        .unwrap();
+    assert_eq!(client.stats().next().unwrap().state, BreakerState::Open);
+
+    rpc.set("https://first.invalid/", UP);
+    pool.run_until(client.request("eth_blockNumber", json!([])))
+        .unwrap();
+    assert_eq!(client.stats().next().unwrap().state, BreakerState::Closed);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn all_breakers_open() {
+    let mut pool = LocalPool::new();
+    let list = fetch_list(LIST);
+
+    let rpc = MockRpc::with_nodes([
+        ("https://first.invalid/", Node::Down),
+        ("https://second.invalid/", Node::Down),
+    ]);
+
+    let mut client = FailoverClient::new(rpc.clone(), &list, 1)
+        .failure_threshold(1)
+        .cooldown(Duration::from_secs(3600));
+
+    pool.run_until(client.request("eth_blockNumber", json!([])))
+        .unwrap_err();
+
+    let err = pool
+        .run_until(client.request("eth_blockNumber", json!([])))
+        .unwrap_err();
+
+    let attempts = match err {
+        FailoverError::Exhausted { attempts, .. } => attempts,
+        other => panic!("expected Exhausted, got {:?}", other),
+    };
+
+    let endpoints: Vec<_> = attempts.iter().map(|(e, _)| e.as_str()).collect();
+    assert_eq!(
+        endpoints,
+        ["https://first.invalid/", "https://second.invalid/"]
+    );
+
+    for (_, error) in &attempts {
+        match error {
+            RpcError::CircuitOpen { retry_after, .. } => {
+                assert!(*retry_after <= Duration::from_secs(3600))
+            }
+            other => panic!("expected CircuitOpen, got {:?}", other),
+        }
+    }
+
+    assert_eq!(rpc.requests().len(), 2);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn endpoints_redacted() {
+    let keyed = LIST.replace(
+        "https://first.invalid/",
+        "https://first.invalid/?apikey=hunter2",
+    );
+
+    let list = fetch_list(keyed);
+    let mut pool = LocalPool::new();
+
+    let rpc = MockRpc::with_nodes([
+        ("https://first.invalid/?apikey=hunter2", Node::Down),
+        ("https://second.invalid/", Node::Down),
+    ]);
+
+    let mut client = FailoverClient::new(rpc, &list, 1);
+    let err = pool
+        .run_until(client.request("eth_blockNumber", json!([])))
+        .unwrap_err();
+
+    let display = err.to_string();
+    assert!(!display.contains("hunter2"), "{}", display);
+    assert!(display.contains("https://first.invalid/?apikey=REDACTED"));
+    assert!(!format!("{:?}", err).contains("hunter2"));
+
+    let stats: Vec<_> = client.stats().collect();
+    assert_eq!(stats[0].endpoint, "https://first.invalid/?apikey=hunter2");
+    assert!(!format!("{:?}", stats).contains("hunter2"));
+}
@@ -0,0 +1,65 @@
+pub mod utils;
+
+use eip5139::{RpcProviders, Source};
+
+use futures_executor::LocalPool;
+
+use self::utils::Fetch;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn list(logo: &str, timestamp: &str) -> String {
+    format!(
+        r#"{{
+  "name": "Root List",
+  "logo": "{}",
+  "version": {{
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  }},
+  "timestamp": "{}",
+  "providers": {{}}
+}}"#,
+        logo, timestamp
+    )
+}
+
+fn resolve(contents: String) -> Result<RpcProviders, eip5139::Error> {
+    let fetch = Fetch::with_one(contents);
+    let mut pool = LocalPool::new();
+    pool.run_until(RpcProviders::fetch(fetch, Source::Uri("file://one".into())))
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn reject_bad_timestamp() {
+    let err = resolve(list("https://example.invalid/logo.png", "yesterday")).unwrap_err();
+
+    #[cfg(feature = "formats")]
+    {
+        assert_eq!(err.code(), "VALIDATION");
+        assert!(err.to_string().contains("/timestamp"));
+    }
+
+    #[cfg(not(feature = "formats"))]
+    assert_eq!(err.code(), "JSON");
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn bad_logo() {
+    let result = resolve(list("not a url", "2004-08-08T00:00:00Z"));
+
+    #[cfg(feature = "formats")]
+    {
+        let err = result.unwrap_err();
+        assert_eq!(err.code(), "VALIDATION");
+        assert!(err.to_string().contains("/logo"));
+    }
+
+    // Logos aren't used by this crate, so nothing else checks them.
+    #[cfg(not(feature = "formats"))]
+    assert_eq!(result.unwrap().logo.as_deref(), Some("not a url"));
+}
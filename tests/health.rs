@@ -0,0 +1,113 @@
+pub mod utils;
+
+use eip5139::health::{HealthCheck, Status};
+use eip5139::{RpcProviders, Source};
+
+use futures_executor::LocalPool;
+
+use self::utils::{Fetch, MockRpc, Node};
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const LIST: &str = r#"{
+  "name": "Root List",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "providers": {
+    "frustrata": {
+      "name": "Frustrata",
+      "chains": [
+        {
+          "chainId": 1,
+          "endpoints": [
+            "https://good.frustrata.invalid/",
+            "https://slow.frustrata.invalid/",
+            "https://testnet.frustrata.invalid/",
+            "https://dead.frustrata.invalid/",
+            "https://broken.frustrata.invalid/"
+          ]
+        }
+      ]
+    }
+  }
+}"#;
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn report() {
+    let mut pool = LocalPool::new();
+
+    let fetch = Fetch::with_one(LIST);
+    let list = pool
+        .run_until(RpcProviders::fetch(fetch, Source::Uri("file://one".into())))
+        .unwrap();
+
+    let rpc = MockRpc::with_nodes([
+        (
+            "https://good.frustrata.invalid/",
+            Node::Up {
+                chain_id: 1,
+                block: 100,
+            },
+        ),
+        (
+            "https://slow.frustrata.invalid/",
+            Node::Up {
+                chain_id: 1,
+                block: 90,
+            },
+        ),
+        (
+            "https://testnet.frustrata.invalid/",
+            Node::Up {
+                chain_id: 5,
+                block: 200,
+            },
+        ),
+        (
+            "https://broken.frustrata.invalid/",
+            Node::Error { code: -32603 },
+        ),
+    ]);
+
+    let mut check = HealthCheck::new(rpc).max_lag(5);
+    let report = pool.run_until(check.check(&list));
+
+    assert_eq!(report.endpoints.len(), 5);
+
+    let good = &report.endpoints[0];
+    assert!(good.status.is_healthy());
+    assert_eq!(good.provider, "frustrata");
+    assert_eq!(good.chain_id, 1);
+    assert_eq!(good.block_number, Some(100));
+    assert!(good.latency.is_some());
+
+    match report.endpoints[1].status {
+        Status::Lagging { behind, .. } => assert_eq!(behind, 10),
+        ref other => panic!("expected Lagging, got {:?}", other),
+    }
+
+    match report.endpoints[2].status {
+        Status::WrongChain { actual, .. } => assert_eq!(actual, 5),
+        ref other => panic!("expected WrongChain, got {:?}", other),
+    }
+
+    assert!(matches!(
+        report.endpoints[3].status,
+        Status::Unreachable { .. }
+    ));
+    assert!(report.endpoints[3].latency.is_none());
+
+    assert!(matches!(
+        report.endpoints[4].status,
+        Status::Unreachable { .. }
+    ));
+
+    assert_eq!(report.healthy().count(), 1);
+    assert_eq!(report.unhealthy().count(), 4);
+}
@@ -0,0 +1,274 @@
+pub mod utils;
+
+use eip5139::lint::{Finding, Linter, Rule, Severity};
+use eip5139::RpcProviders;
+
+use serde_json::Value;
+
+use std::time::{Duration, SystemTime};
+
+use self::utils::fetch_list;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const LIST: &str = r#"{
+  "name": "Root List",
+  "logo": "http://example.invalid/logo.png",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "providers": {
+    "frustrata": {
+      "name": "Frustrata",
+      "priority": 1,
+      "chains": [
+        {
+          "chainId": 1,
+          "endpoints": [
+            "http://mainnet.frustrata.invalid/rpc",
+            "https://127.0.0.1:8545"
+          ]
+        }
+      ]
+    },
+    "frustrata2": {
+      "name": "frustrata",
+      "priority": 1,
+      "chains": [
+        {
+          "chainId": 1,
+          "endpoints": ["https://localhost/", "https://localhost"]
+        }
+      ]
+    },
+    "nothing": {
+      "name": "Nothing",
+      "chains": []
+    }
+  }
+}"#;
+
+const EXTENSION: &str = r#"{
+  "name": "Extension List",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2003-08-08T00:00:00.0Z",
+  "extends": {
+    "uri": "file://two",
+    "version": {
+      "major": 1,
+      "minor": 0,
+      "patch": 0
+    }
+  },
+  "changes": []
+}"#;
+
+fn rules(linter: &Linter, list: &RpcProviders) -> Vec<(&'static str, String)> {
+    linter
+        .lint(list)
+        .into_iter()
+        .map(|d| (d.rule, d.pointer))
+        .collect()
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn lint_resolved() {
+    let list = fetch_list(LIST);
+    let found = rules(&Linter::new(), &list);
+
+    let expected = [
+        ("empty-provider", "/providers/nothing"),
+        (
+            "insecure-endpoint",
+            "/providers/frustrata/chains/0/endpoints/0",
+        ),
+        (
+            "local-endpoint",
+            "/providers/frustrata/chains/0/endpoints/1",
+        ),
+        (
+            "local-endpoint",
+            "/providers/frustrata2/chains/0/endpoints/0",
+        ),
+        (
+            "local-endpoint",
+            "/providers/frustrata2/chains/0/endpoints/1",
+        ),
+        ("duplicate-provider-name", "/providers/frustrata2/name"),
+        ("priority-collision", "/providers/frustrata2/priority"),
+        ("insecure-logo", "/logo"),
+        ("duplicate-endpoint", "/providers/frustrata2"),
+    ];
+
+    let expected: Vec<_> = expected.iter().map(|(r, p)| (*r, p.to_string())).collect();
+
+    assert_eq!(found, expected);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn severity_and_disable() {
+    let list = fetch_list(LIST);
+    let linter = Linter::new()
+        .severity("insecure-endpoint", Severity::Error)
+        .disable("local-endpoint")
+        .disable("duplicate-endpoint");
+
+    let diagnostics = linter.lint(&list);
+
+    assert!(diagnostics.iter().all(|d| d.rule != "local-endpoint"));
+
+    let insecure = diagnostics
+        .iter()
+        .find(|d| d.rule == "insecure-endpoint")
+        .unwrap();
+    assert_eq!(insecure.severity, Severity::Error);
+
+    let collision = diagnostics
+        .iter()
+        .find(|d| d.rule == "priority-collision")
+        .unwrap();
+    assert_eq!(collision.severity, Severity::Info);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn future_timestamp() {
+    let list = fetch_list(LIST);
+    let then = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+    let found = rules(
+        &Linter::empty()
+            .rule(eip5139::lint::TimestampOrder)
+            .now(then),
+        &list,
+    );
+
+    assert_eq!(found, [("timestamp-order", "/timestamp".to_owned())]);
+
+    let found = rules(&Linter::empty().rule(eip5139::lint::TimestampOrder), &list);
+    assert!(found.is_empty());
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn document_older_than_parent() {
+    let document: Value = serde_json::from_str(EXTENSION).unwrap();
+    let parent: Value = serde_json::from_str(LIST).unwrap();
+
+    let diagnostics = Linter::new().lint_document(&document, Some(&parent));
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].rule, "timestamp-order");
+    assert!(diagnostics[0].message.contains("parent"));
+
+    let diagnostics = Linter::new().lint_document(&parent, None);
+    assert!(diagnostics.iter().any(|d| d.rule == "insecure-logo"));
+    assert!(diagnostics.iter().any(|d| d.rule == "empty-provider"));
+}
+
+#[derive(Debug)]
+struct NoFrustration;
+
+impl Rule for NoFrustration {
+    fn id(&self) -> &'static str {
+        "no-frustration"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, context: &eip5139::lint::Context<'_>) -> Vec<Finding> {
+        context
+            .providers()
+            .iter()
+            .filter(|p| p.key.starts_with("frustrata"))
+            .map(|p| Finding::new(format!("/providers/{}", p.key), "frustrating"))
+            .collect()
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn custom_rule() {
+    let list = fetch_list(LIST);
+    let found = rules(&Linter::empty().rule(NoFrustration), &list);
+
+    assert_eq!(
+        found,
+        [
+            ("no-frustration", "/providers/frustrata".to_owned()),
+            ("no-frustration", "/providers/frustrata2".to_owned()),
+        ]
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn messages_redacted() {
+    let keyed = LIST.replace(
+        r#""http://mainnet.frustrata.invalid/rpc",
+            "https://127.0.0.1:8545""#,
+        r#""http://127.0.0.1:8545/?apikey=hunter2",
+            "HTTP://127.0.0.1:8545/?apikey=hunter2""#,
+    );
+
+    let list = fetch_list(keyed);
+
+    let diagnostics = Linter::new().lint(&list);
+
+    for rule in ["insecure-endpoint", "local-endpoint", "duplicate-endpoint"] {
+        let diagnostic = diagnostics
+            .iter()
+            .find(|d| d.rule == rule && d.message.contains("127.0.0.1"))
+            .unwrap();
+
+        assert!(
+            diagnostic.message.contains("apikey=REDACTED"),
+            "{}",
+            diagnostic.message
+        );
+    }
+
+    assert!(diagnostics.iter().all(|d| !d.message.contains("hunter2")));
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn local_hosts_normalized() {
+    let spelled = LIST.replace(
+        r#""http://mainnet.frustrata.invalid/rpc",
+            "https://127.0.0.1:8545""#,
+        r#""https://LOCALHOST:8545/",
+            "https://localhost./",
+            "https://node.Localhost.:8545/",
+            "https://localhost.invalid/""#,
+    );
+
+    let list = fetch_list(spelled);
+    let found = rules(&Linter::empty().rule(eip5139::lint::LocalEndpoint), &list);
+
+    let expected = [
+        "/providers/frustrata/chains/0/endpoints/0",
+        "/providers/frustrata/chains/0/endpoints/1",
+        "/providers/frustrata/chains/0/endpoints/2",
+        "/providers/frustrata2/chains/0/endpoints/0",
+        "/providers/frustrata2/chains/0/endpoints/1",
+    ];
+
+    let expected: Vec<_> = expected
+        .iter()
+        .map(|p| ("local-endpoint", p.to_string()))
+        .collect();
+
+    assert_eq!(found, expected);
+}
@@ -0,0 +1,97 @@
+pub mod utils;
+
+use eip5139::normalize::{Action, Normalizer, Scope};
+
+use self::utils::fetch_list;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const LIST: &str = r#"{
+  "name": "Root List",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "providers": {
+    "frustrata": {
+      "name": "Frustrata",
+      "priority": 1,
+      "chains": [
+        {
+          "chainId": 1,
+          "endpoints": [
+            "https://mainnet.frustrata.invalid/rpc",
+            "HTTPS://Mainnet.Frustrata.invalid:443/rpc/",
+            "https://shared.invalid"
+          ]
+        }
+      ]
+    },
+    "sourceri": {
+      "name": "Sourceri",
+      "priority": 0,
+      "chains": [
+        {
+          "chainId": 1,
+          "endpoints": ["https://shared.invalid/"]
+        },
+        {
+          "chainId": 5,
+          "endpoints": ["https://mainnet.frustrata.invalid/rpc"]
+        }
+      ]
+    }
+  }
+}"#;
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn check_reports_without_changing() {
+    let list = fetch_list(LIST);
+    let report = Normalizer::new().within(Action::Collapse).check(&list);
+
+    assert_eq!(report.duplicates.len(), 2);
+
+    let shared = &report.duplicates[0];
+    assert_eq!(shared.canonical, "https://shared.invalid/");
+    assert_eq!(shared.scope, Scope::AcrossProviders);
+    assert_eq!(shared.occurrences[0].provider, "sourceri");
+    assert_eq!(shared.occurrences[1].provider, "frustrata");
+
+    let rpc = &report.duplicates[1];
+    assert_eq!(rpc.canonical, "https://mainnet.frustrata.invalid/rpc");
+    assert_eq!(rpc.scope, Scope::WithinProvider);
+    assert_eq!(rpc.chain_id, 1);
+
+    assert_eq!(report.noncanonical.len(), 2);
+    assert_eq!(list.endpoints_for(1).count(), 4);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn apply_collapses() {
+    let mut list = fetch_list(LIST);
+    Normalizer::new()
+        .rewrite(true)
+        .within(Action::Collapse)
+        .across(Action::Collapse)
+        .apply(&mut list);
+
+    let endpoints: Vec<_> = list
+        .endpoints_for(1)
+        .map(|e| (e.provider.key.as_str(), e.endpoint))
+        .collect();
+
+    assert_eq!(
+        endpoints,
+        [
+            ("sourceri", "https://shared.invalid/"),
+            ("frustrata", "https://mainnet.frustrata.invalid/rpc"),
+        ]
+    );
+
+    assert_eq!(list.endpoints_for(5).count(), 1);
+}
@@ -0,0 +1,125 @@
+pub mod utils;
+
+use eip5139::overrides::{Overrides, StaleOverride};
+use eip5139::{Provider, ProviderChain};
+
+use self::utils::fetch_list;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const LIST: &str = r#"{
+  "name": "Root List",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "providers": {
+    "frustrata": {
+      "name": "Frustrata",
+      "priority": 1,
+      "chains": [
+        {
+          "chainId": 1,
+          "endpoints": [
+            "https://mainnet1.frustrata.invalid/",
+            "https://mainnet2.frustrata.invalid/"
+          ]
+        }
+      ]
+    },
+    "sourceri": {
+      "name": "Sourceri",
+      "priority": 2,
+      "chains": [
+        {
+          "chainId": 1,
+          "endpoints": ["https://mainnet.sourceri.invalid/"]
+        }
+      ]
+    },
+    "tracker": {
+      "name": "Tracker",
+      "priority": 0,
+      "chains": [
+        {
+          "chainId": 1,
+          "endpoints": ["https://mainnet.tracker.invalid/"]
+        }
+      ]
+    }
+  }
+}"#;
+
+fn overrides() -> Overrides {
+    let mut mine = Provider::new("mine", "My Node");
+    mine.chains
+        .push(ProviderChain::new(1, ["http://localhost:8545"]));
+
+    let mut overrides = Overrides::default();
+    overrides.add_provider(mine);
+    overrides.remove_provider("tracker");
+    overrides.remove_endpoint("frustrata", 1, "https://mainnet2.frustrata.invalid/");
+    overrides.add_endpoint("sourceri", 5, "https://goerli.sourceri.invalid/");
+    overrides.set_priority("mine", 0);
+    overrides.set_priority("sourceri", 1);
+    overrides
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn apply() {
+    let mut list = fetch_list(LIST);
+    let stale = overrides().apply(&mut list);
+    assert_eq!(stale, []);
+
+    let keys: Vec<_> = list.providers_for(1).map(|p| p.key.as_str()).collect();
+    assert_eq!(keys, ["mine", "frustrata", "sourceri"]);
+
+    let endpoints: Vec<_> = list.endpoints_for(1).map(|e| e.endpoint).collect();
+    assert_eq!(
+        endpoints,
+        [
+            "http://localhost:8545",
+            "https://mainnet1.frustrata.invalid/",
+            "https://mainnet.sourceri.invalid/",
+        ]
+    );
+
+    let goerli: Vec<_> = list.endpoints_for(5).map(|e| e.endpoint).collect();
+    assert_eq!(goerli, ["https://goerli.sourceri.invalid/"]);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn round_trip_and_stale() {
+    let json = serde_json::to_string(&overrides()).unwrap();
+    let restored: Overrides = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, overrides());
+
+    // Upstream drops `tracker` and `sourceri` entirely.
+    let mut list = fetch_list(LIST);
+    let mut providers = list.providers().to_vec();
+    providers.retain(|p| p.key == "frustrata");
+    list.set_providers(providers);
+
+    let stale = restored.apply(&mut list);
+    assert_eq!(stale.len(), 3);
+    assert!(matches!(
+        &stale[0],
+        StaleOverride::RemovedProviderMissing { provider, .. } if provider == "tracker"
+    ));
+    assert!(matches!(
+        &stale[1],
+        StaleOverride::AddedEndpointProviderMissing(e) if e.provider == "sourceri"
+    ));
+    assert!(matches!(
+        &stale[2],
+        StaleOverride::PriorityProviderMissing { provider, .. } if provider == "sourceri"
+    ));
+
+    let keys: Vec<_> = list.providers().iter().map(|p| p.key.as_str()).collect();
+    assert_eq!(keys, ["mine", "frustrata"]);
+}
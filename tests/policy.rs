@@ -0,0 +1,295 @@
+pub mod utils;
+
+use eip5139::errors::PolicyError;
+use eip5139::policy::{Rules, SourcePolicy};
+use eip5139::{Error, Resolver, RpcProviders, Source};
+
+use futures_executor::LocalPool;
+
+use self::utils::SharedFetch;
+
+use std::io;
+use std::net::IpAddr;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const ROOT: &str = r#"{
+  "name": "Root List",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2004-08-08T00:00:00Z",
+  "providers": {}
+}"#;
+
+fn extension(parent: &str) -> String {
+    format!(
+        r#"{{
+  "name": "Extension List",
+  "version": {{
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  }},
+  "timestamp": "2005-08-08T00:00:00Z",
+  "extends": {{
+    "uri": "{}",
+    "version": {{
+      "major": 1,
+      "minor": 0,
+      "patch": 0
+    }}
+  }},
+  "changes": []
+}}"#,
+        parent
+    )
+}
+
+fn uri(uri: &str) -> Source {
+    Source::Uri(uri.into())
+}
+
+fn resolve<P>(fetch: SharedFetch, policy: P, source: Source) -> Result<RpcProviders, Error>
+where
+    P: SourcePolicy,
+{
+    let mut pool = LocalPool::new();
+    pool.run_until(Resolver::new(fetch).policy(policy).resolve(source))
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn default_rules_accept_everything() {
+    let rules = Rules::new();
+
+    rules.check(&uri("http://127.0.0.1/list.json")).unwrap();
+    rules.check(&uri("file:///etc/list.json")).unwrap();
+    rules.check(&Source::Ens("example.eth".into())).unwrap();
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn reject_scheme() {
+    let rules = Rules::new().allow_scheme("https");
+
+    rules.check(&uri("https://example.com/list.json")).unwrap();
+
+    for rejected in ["http://example.com/list.json", "file:///etc/passwd"] {
+        match rules.check(&uri(rejected)) {
+            Err(PolicyError::Scheme { .. }) => (),
+            other => panic!("{}: {:?}", rejected, other),
+        }
+    }
+
+    match rules.check(&Source::Ens("example.eth".into())) {
+        Err(PolicyError::Scheme { scheme, .. }) => assert_eq!(scheme, "ens"),
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn deny_host() {
+    let rules = Rules::new()
+        .deny_host("metadata.example")
+        .deny_host("*.internal.example.com");
+
+    rules.check(&uri("https://example.com/list.json")).unwrap();
+    rules.check(&uri("https://internal.example.com/")).unwrap();
+
+    for rejected in [
+        "https://metadata.example/",
+        "https://METADATA.example/",
+        "https://a.internal.example.com/",
+        "https://a.b.internal.example.com/",
+        "https://a.internal.example.com./",
+        "https://metadata.example./",
+    ] {
+        match rules.check(&uri(rejected)) {
+            Err(PolicyError::Host { .. }) => (),
+            other => panic!("{}: {:?}", rejected, other),
+        }
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn allow_host() {
+    let rules = Rules::new()
+        .allow_host("example.com")
+        .allow_host("*.lists.example.org");
+
+    rules.check(&uri("https://example.com/list.json")).unwrap();
+    rules.check(&uri("https://example.com./list.json")).unwrap();
+    rules
+        .check(&uri("https://a.lists.example.org/list.json"))
+        .unwrap();
+
+    for rejected in [
+        "https://www.example.com/",
+        "https://lists.example.org/",
+        "https://evilexample.com/",
+        "https://a.evillists.example.org/",
+    ] {
+        match rules.check(&uri(rejected)) {
+            Err(PolicyError::Host { host, .. }) => assert!(rejected.contains(&host)),
+            other => panic!("{}: {:?}", rejected, other),
+        }
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn allow_ens() {
+    let rules = Rules::new().allow_ens("lists.eth");
+
+    rules.check(&Source::Ens("lists.eth".into())).unwrap();
+    rules.check(&Source::Ens("Lists.eth".into())).unwrap();
+    rules.check(&uri("https://example.com/list.json")).unwrap();
+
+    match rules.check(&Source::Ens("other.eth".into())) {
+        Err(PolicyError::Ens { name, .. }) => assert_eq!(name, "other.eth"),
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn deny_private_addresses() {
+    let rules = Rules::new().deny_private_addresses();
+
+    rules.check(&uri("https://example.com/list.json")).unwrap();
+    rules
+        .check(&uri("https://93.184.216.34/list.json"))
+        .unwrap();
+    rules
+        .check(&uri("https://[2606:2800:220:1::]/list.json"))
+        .unwrap();
+    rules
+        .check(&uri("https://[64:ff9b::93.184.216.34]/list.json"))
+        .unwrap();
+
+    for rejected in [
+        "http://127.0.0.1/",
+        "http://10.0.0.1/",
+        "http://172.16.5.4/",
+        "http://192.168.1.1/",
+        "http://169.254.169.254/latest/meta-data/",
+        "http://0.0.0.0/",
+        "http://100.64.0.1/",
+        "http://[::1]/",
+        "http://[fd00::1]/",
+        "http://[fe80::1]/",
+        "http://[::ffff:127.0.0.1]/",
+        "http://[::127.0.0.1]/",
+        "http://[::10.0.0.1]/",
+        "http://[64:ff9b::169.254.169.254]/",
+    ] {
+        match rules.check(&uri(rejected)) {
+            Err(PolicyError::PrivateAddress { .. }) => (),
+            other => panic!("{}: {:?}", rejected, other),
+        }
+    }
+
+    for rejected in [
+        "http://localhost:8545/",
+        "http://localhost./",
+        "http://node.localhost/",
+        "http://node.localhost./",
+    ] {
+        match rules.check(&uri(rejected)) {
+            Err(PolicyError::Host { .. }) => (),
+            other => panic!("{}: {:?}", rejected, other),
+        }
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn reject_requested_list() {
+    // Nothing is served, so fetching would panic.
+    let fetch = SharedFetch::default();
+    let rules = Rules::new().allow_scheme("https");
+
+    match resolve(fetch, rules, uri("http://example.com/list.json")) {
+        Err(Error::Policy { list, source, .. }) => {
+            assert_eq!(list, uri("http://example.com/list.json"));
+            assert!(matches!(source, PolicyError::Scheme { .. }));
+        }
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn reject_private_parent() {
+    let fetch = SharedFetch::default();
+    fetch.set(
+        "https://example.com/list.json",
+        extension("http://169.254.169.254/list.json"),
+    );
+
+    let rules = Rules::new().deny_private_addresses();
+
+    match resolve(fetch, rules, uri("https://example.com/list.json")) {
+        Err(Error::Policy { list, source, .. }) => {
+            assert_eq!(list, uri("http://169.254.169.254/list.json"));
+            match source {
+                PolicyError::PrivateAddress { address, .. } => {
+                    assert_eq!(address, "169.254.169.254".parse::<IpAddr>().unwrap())
+                }
+                other => panic!("{:?}", other),
+            }
+        }
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn accept_allowed_chain() {
+    let fetch = SharedFetch::default();
+    fetch.set(
+        "https://example.com/list.json",
+        extension("https://lists.example.org/root.json"),
+    );
+    fetch.set("https://lists.example.org/root.json", ROOT);
+
+    let rules = Rules::new()
+        .allow_scheme("https")
+        .allow_host("example.com")
+        .allow_host("lists.example.org")
+        .deny_private_addresses();
+
+    let list = resolve(fetch, rules, uri("https://example.com/list.json")).unwrap();
+    assert_eq!(list.name, "Extension List");
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn custom_policy() {
+    let fetch = SharedFetch::default();
+    fetch.set("https://example.com/list.json", ROOT);
+
+    let policy = |source: &Source| match source {
+        Source::Uri(uri) if uri.ends_with(".json") => Ok(()),
+        _ => Err(PolicyError::custom(io::Error::new(
+            io::ErrorKind::Other,
+            "not a json file",
+        ))),
+    };
+
+    resolve(fetch.clone(), policy, uri("https://example.com/list.json")).unwrap();
+
+    match resolve(fetch, policy, uri("https://example.com/list")) {
+        Err(Error::Policy {
+            source: PolicyError::Custom { .. },
+            ..
+        }) => (),
+        other => panic!("{:?}", other),
+    }
+}
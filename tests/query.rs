@@ -0,0 +1,116 @@
+pub mod utils;
+
+use eip5139::RpcProviders;
+
+use self::utils::fetch_list;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const LIST: &str = r#"{
+  "name": "Root List",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "providers": {
+    "frustrata": {
+      "name": "Frustrata",
+      "chains": [
+        {
+          "chainId": 1,
+          "endpoints": [
+            "https://mainnet1.frustrata.invalid/",
+            "https://mainnet2.frustrata.invalid/"
+          ]
+        },
+        {
+          "chainId": 137,
+          "endpoints": ["https://polygon.frustrata.invalid/"]
+        }
+      ]
+    },
+    "sourceri": {
+      "name": "Sourceri",
+      "priority": 3,
+      "chains": [
+        {
+          "chainId": 137,
+          "endpoints": ["https://polygon.sourceri.invalid/"]
+        }
+      ]
+    },
+    "floop": {
+      "name": "Floop",
+      "priority": 0,
+      "chains": [
+        {
+          "chainId": 137,
+          "endpoints": ["https://polygon.floop.invalid/"]
+        },
+        {
+          "chainId": 10,
+          "endpoints": ["https://optimism.floop.invalid/"]
+        }
+      ]
+    }
+  }
+}"#;
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn chain_ids() {
+    let list = fetch_list(LIST);
+    assert_eq!(list.chain_ids().collect::<Vec<_>>(), [1, 10, 137]);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn providers_and_endpoints_for_chain() {
+    let list = fetch_list(LIST);
+
+    let keys: Vec<_> = list.providers_for(137).map(|p| p.key.as_str()).collect();
+    assert_eq!(keys, ["floop", "sourceri", "frustrata"]);
+
+    let endpoints: Vec<_> = list
+        .endpoints_for(137)
+        .map(|e| (e.provider.name.as_str(), e.endpoint))
+        .collect();
+
+    assert_eq!(
+        endpoints,
+        [
+            ("Floop", "https://polygon.floop.invalid/"),
+            ("Sourceri", "https://polygon.sourceri.invalid/"),
+            ("Frustrata", "https://polygon.frustrata.invalid/"),
+        ]
+    );
+
+    assert_eq!(list.endpoints_for(1).count(), 2);
+    assert_eq!(list.endpoints_for(5).count(), 0);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn index_rebuilt_after_modification() {
+    let mut list = fetch_list(LIST);
+    assert_eq!(list.providers_for(10).count(), 1);
+
+    let mut providers = list.providers().to_vec();
+    providers.retain(|p| p.key != "floop");
+    list.set_providers(providers);
+
+    assert_eq!(list.providers_for(10).count(), 0);
+    assert_eq!(list.chain_ids().collect::<Vec<_>>(), [1, 137]);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    // Resolved lists are shared between threads, eg. through an `Arc`.
+    assert_send_sync::<RpcProviders>();
+}
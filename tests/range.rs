@@ -0,0 +1,92 @@
+pub mod utils;
+
+use eip5139::errors::*;
+use eip5139::{RangeGrammar, Resolver, Source};
+
+use futures_executor::LocalPool;
+
+use self::utils::Fetch;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const ROOT: &str = r#"{
+  "name": "Root List",
+  "version": {
+    "major": 2,
+    "minor": 4,
+    "patch": 1
+  },
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "providers": {
+  }
+}"#;
+
+fn extension(version: &str) -> String {
+    format!(
+        r#"{{
+  "name": "Extension List",
+  "version": {{
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  }},
+  "timestamp": "2024-08-08T00:00:00.0Z",
+  "extends": {{
+    "uri": "file://one",
+    "version": {}
+  }},
+  "changes": []
+}}"#,
+        version
+    )
+}
+
+fn resolve(grammar: RangeGrammar, version: &str) -> Result<eip5139::RpcProviders, Error> {
+    let fetch = Fetch::with_two(ROOT, extension(version));
+    let resolver = Resolver::new(fetch).range_grammar(grammar);
+    let mut pool = LocalPool::new();
+    pool.run_until(resolver.resolve(Source::Uri("file://two".into())))
+}
+
+const COMPOUND: &str = r#"[
+      { "major": 2, "minor": 3, "patch": 0, "mode": ">=" },
+      { "major": 3, "minor": 0, "patch": 0, "mode": "<" }
+    ]"#;
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn extended_compound_matches() {
+    let list = resolve(RangeGrammar::Extended, COMPOUND).unwrap();
+    assert_eq!(list.name, "Extension List");
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn strict_rejects_extended_syntax() {
+    let err = resolve(RangeGrammar::Strict, COMPOUND).unwrap_err();
+    assert_eq!(err.code(), "VALIDATION");
+
+    let tilde = r#"{ "major": 2, "minor": 4, "patch": 0, "mode": "~" }"#;
+    let err = resolve(RangeGrammar::Strict, tilde).unwrap_err();
+    assert_eq!(err.code(), "VALIDATION");
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn extended_reports_unmatched_range() {
+    let tilde = r#"{ "major": 2, "minor": 4, "patch": 0, "mode": "~" }"#;
+    resolve(RangeGrammar::Extended, tilde).unwrap();
+
+    let compound = r#"[
+      { "major": 2, "minor": 0, "patch": 0, "mode": ">=" },
+      { "major": 2, "minor": 4, "patch": 0, "mode": "<" }
+    ]"#;
+
+    match resolve(RangeGrammar::Extended, compound).unwrap_err() {
+        Error::VersionMismatch { requested, .. } => {
+            assert_eq!(requested.to_string(), "<2.4.0");
+        }
+        other => panic!("expected VersionMismatch, but got: {:?}", other),
+    }
+}
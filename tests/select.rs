@@ -0,0 +1,116 @@
+pub mod utils;
+
+use eip5139::select::{RoundRobin, Selector, Sticky, StrictPriority, WeightedRandom};
+
+use self::utils::fetch_list;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const LIST: &str = r#"{
+  "name": "Root List",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "providers": {
+    "frustrata": {
+      "name": "Frustrata",
+      "chains": [
+        {
+          "chainId": 137,
+          "endpoints": ["https://polygon.frustrata.invalid/"]
+        }
+      ]
+    },
+    "sourceri": {
+      "name": "Sourceri",
+      "priority": 0,
+      "chains": [
+        {
+          "chainId": 137,
+          "endpoints": [
+            "https://polygon1.sourceri.invalid/",
+            "https://polygon2.sourceri.invalid/"
+          ]
+        }
+      ]
+    },
+    "floop": {
+      "name": "Floop",
+      "priority": 0,
+      "chains": [
+        {
+          "chainId": 137,
+          "endpoints": ["https://polygon.floop.invalid/"]
+        }
+      ]
+    }
+  }
+}"#;
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn strict_priority() {
+    let list = fetch_list(LIST);
+    let mut selector = Selector::new(&list, StrictPriority);
+
+    let choice = selector.select(137).unwrap();
+    assert_eq!(choice.endpoint, "https://polygon.floop.invalid/");
+    assert!(selector.select(1).is_none());
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn round_robin_within_tier() {
+    let list = fetch_list(LIST);
+    let mut selector = Selector::new(&list, RoundRobin::default());
+
+    let chosen: Vec<_> = (0..4)
+        .map(|_| selector.select(137).unwrap().endpoint)
+        .collect();
+
+    assert_eq!(
+        chosen,
+        [
+            "https://polygon.floop.invalid/",
+            "https://polygon1.sourceri.invalid/",
+            "https://polygon2.sourceri.invalid/",
+            "https://polygon.floop.invalid/",
+        ]
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn weighted_random_is_deterministic() {
+    let list = fetch_list(LIST);
+
+    // Weights: floop 1, sourceri 1/2 + 1/2, frustrata 1/2 (as priority 1.)
+    let draws = vec![0, u64::MAX / 2, u64::MAX / 20 * 13, u64::MAX];
+    let mut draws = draws.into_iter();
+    let random = move || draws.next().unwrap();
+
+    let mut selector = Selector::new(&list, WeightedRandom::new(random));
+
+    let chosen: Vec<_> = (0..4)
+        .map(|_| selector.select(137).unwrap().provider.key.as_str())
+        .collect();
+
+    assert_eq!(chosen, ["floop", "sourceri", "sourceri", "frustrata"]);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn sticky_session() {
+    let list = fetch_list(LIST);
+    let mut selector = Selector::new(&list, Sticky::new(RoundRobin::default()));
+
+    let first = selector.select(137).unwrap().endpoint;
+    assert_eq!(selector.select(137).unwrap().endpoint, first);
+
+    selector.strategy_mut().forget(137);
+    assert_ne!(selector.select(137).unwrap().endpoint, first);
+}
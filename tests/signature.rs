@@ -0,0 +1,235 @@
+pub mod utils;
+
+use eip5139::errors::SignatureError;
+use eip5139::events::Event;
+use eip5139::signature::{Signer, TrustPolicy};
+use eip5139::{Error, Resolver, RpcProviders, Source};
+
+use futures_executor::LocalPool;
+
+use k256::ecdsa::signature::Signer as _;
+use k256::ecdsa::{recoverable, SigningKey};
+
+use self::utils::Fetch;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+// Already canonical, so the signed bytes are exactly this text.
+const ROOT: &str = r#"{"name":"Root List","providers":{},"timestamp":"2004-08-08T00:00:00Z","version":{"major":1,"minor":0,"patch":0}}"#;
+
+const EXTENSION: &str = r#"{
+  "name": "Extension List",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2005-08-08T00:00:00Z",
+  "extends": {
+    "uri": "file://one",
+    "version": {
+      "major": 1,
+      "minor": 0,
+      "patch": 0
+    }
+  },
+  "changes": []
+}"#;
+
+const EXTENSION_CANONICAL: &str = r#"{"changes":[],"extends":{"uri":"file://one","version":{"major":1,"minor":0,"patch":0}},"name":"Extension List","timestamp":"2005-08-08T00:00:00Z","version":{"major":1,"minor":0,"patch":0}}"#;
+
+/// The address of this key is `0x7e5f4552091a69125d5dfcb7b8c2659029395bdf`.
+fn key() -> SigningKey {
+    let mut secret = [0; 32];
+    secret[31] = 1;
+    SigningKey::from_bytes(&secret).unwrap()
+}
+
+fn other_key() -> SigningKey {
+    SigningKey::from_bytes(&[7; 32]).unwrap()
+}
+
+fn sign(key: &SigningKey, message: &str) -> String {
+    let prefixed = format!("\x19Ethereum Signed Message:\n{}{}", message.len(), message);
+    let signature: recoverable::Signature = key.sign(prefixed.as_bytes());
+
+    let mut bytes = signature.as_ref().to_vec();
+    bytes[64] += 27;
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn one() -> Source {
+    Source::Uri("file://one".into())
+}
+
+fn two() -> Source {
+    Source::Uri("file://two".into())
+}
+
+fn signer() -> Signer {
+    "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf"
+        .parse()
+        .unwrap()
+}
+
+fn resolve(fetch: Fetch, policy: TrustPolicy, source: Source) -> Result<RpcProviders, Error> {
+    let mut pool = LocalPool::new();
+    pool.run_until(Resolver::new(fetch).trust(policy).resolve(source))
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn signer_round_trip() {
+    assert_eq!(
+        signer().to_string(),
+        "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf"
+    );
+    assert!("7e5f4552091a69125d5dfcb7b8c2659029395bdf"
+        .parse::<Signer>()
+        .is_err());
+    assert!("0x7e5f".parse::<Signer>().is_err());
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn trusted_chain() {
+    let fetch = Fetch::with_two(ROOT, EXTENSION)
+        .with_signature("file://one", sign(&key(), ROOT))
+        .with_signature("file://two", sign(&key(), EXTENSION_CANONICAL));
+
+    let policy = TrustPolicy::new()
+        .trust(one(), signer())
+        .trust(two(), signer());
+
+    let mut verified = Vec::new();
+    let mut pool = LocalPool::new();
+    let list = pool
+        .run_until(
+            Resolver::new(fetch)
+                .trust(policy)
+                .observer(|event: &Event<'_>| {
+                    if let Event::SignatureVerified { source, .. } = event {
+                        verified.push((*source).clone());
+                    }
+                })
+                .resolve(two()),
+        )
+        .unwrap();
+
+    assert_eq!(list.name, "Extension List");
+    assert_eq!(verified, [two(), one()]);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn unsigned_parent() {
+    let fetch = Fetch::with_two(ROOT, EXTENSION)
+        .with_signature("file://two", sign(&key(), EXTENSION_CANONICAL));
+
+    let policy = TrustPolicy::new()
+        .trust(one(), signer())
+        .trust(two(), signer());
+
+    let err = resolve(fetch, policy, two()).unwrap_err();
+    assert_eq!(err.code(), "UNSIGNED");
+
+    match err {
+        Error::Unsigned { list, .. } => assert_eq!(list, one()),
+        other => panic!("expected Unsigned, but got: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn untrusted_signer() {
+    let fetch = Fetch::with_one(ROOT).with_signature("file://one", sign(&other_key(), ROOT));
+    let policy = TrustPolicy::new().trust(one(), signer());
+
+    let err = resolve(fetch, policy, one()).unwrap_err();
+    assert_eq!(err.code(), "BAD_SIGNATURE");
+
+    match err {
+        Error::BadSignature {
+            list,
+            source: SignatureError::Untrusted { signer, .. },
+            ..
+        } => {
+            assert_eq!(list, one());
+            assert_ne!(signer, "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf");
+        }
+        other => panic!("expected BadSignature, but got: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn tampered_list() {
+    let tampered = ROOT.replace("Root List", "Evil List");
+    let fetch = Fetch::with_one(tampered).with_signature("file://one", sign(&key(), ROOT));
+    let policy = TrustPolicy::new().trust(one(), signer());
+
+    let err = resolve(fetch, policy, one()).unwrap_err();
+    assert_eq!(err.code(), "BAD_SIGNATURE");
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn whitespace_is_not_signed() {
+    let pretty =
+        serde_json::to_string_pretty(&serde_json::from_str::<serde_json::Value>(ROOT).unwrap())
+            .unwrap();
+    let fetch = Fetch::with_one(pretty).with_signature("file://one", sign(&key(), ROOT));
+    let policy = TrustPolicy::new().trust(one(), signer());
+
+    resolve(fetch, policy, one()).unwrap();
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn malformed_signature() {
+    let fetch = Fetch::with_one(ROOT).with_signature("file://one", "0x1234");
+    let policy = TrustPolicy::new().trust(one(), signer());
+
+    let err = resolve(fetch, policy, one()).unwrap_err();
+
+    match err {
+        Error::BadSignature {
+            source: SignatureError::Malformed { .. },
+            ..
+        } => (),
+        other => panic!("expected BadSignature, but got: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn no_policy_no_check() {
+    let fetch = Fetch::with_one(ROOT);
+    let mut pool = LocalPool::new();
+    pool.run_until(RpcProviders::fetch(fetch, one())).unwrap();
+}
+
+#[cfg(feature = "ed25519")]
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn ed25519() {
+    use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signer as _};
+
+    let secret = SecretKey::from_bytes(&[3; 32]).unwrap();
+    let public = PublicKey::from(&secret);
+    let keypair = Keypair { secret, public };
+
+    let signature = hex::encode(keypair.sign(ROOT.as_bytes()).to_bytes());
+    let signer: Signer = format!("ed25519:{}", hex::encode(public.as_bytes()))
+        .parse()
+        .unwrap();
+
+    let fetch = Fetch::with_one(ROOT).with_signature("file://one", signature.clone());
+    let policy = TrustPolicy::new().trust(one(), signer);
+    resolve(fetch, policy, one()).unwrap();
+
+    let fetch = Fetch::with_one(ROOT).with_signature("file://one", signature);
+    let err = resolve(fetch, TrustPolicy::new(), one()).unwrap_err();
+    assert_eq!(err.code(), "BAD_SIGNATURE");
+}
@@ -0,0 +1,282 @@
+pub mod utils;
+
+use eip5139::diff::Entry;
+use eip5139::errors::PolicyError;
+use eip5139::policy::Rules;
+use eip5139::signature::TrustPolicy;
+use eip5139::subscription::{Bump, Subscription};
+use eip5139::{Error, Source};
+
+use futures_executor::LocalPool;
+
+use std::time::{Duration, SystemTime};
+
+use self::utils::SharedFetch;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn list(major: u64, minor: u64, timestamp: &str, providers: &str) -> String {
+    format!(
+        r#"{{
+  "name": "Root List",
+  "version": {{
+    "major": {},
+    "minor": {},
+    "patch": 0
+  }},
+  "timestamp": "{}",
+  "providers": {{ {} }}
+}}"#,
+        major, minor, timestamp, providers
+    )
+}
+
+const FRUSTRATA: &str = r#""frustrata": {
+  "name": "Frustrata",
+  "chains": [{ "chainId": 1, "endpoints": ["https://one.frustrata.invalid/"] }]
+}"#;
+
+const FRUSTRATA_TWO: &str = r#""frustrata": {
+  "name": "Frustrata",
+  "chains": [{ "chainId": 1, "endpoints": ["https://two.frustrata.invalid/"] }]
+}"#;
+
+const SOURCERI: &str = r#""sourceri": {
+  "name": "Sourceri",
+  "chains": [{ "chainId": 1, "endpoints": ["https://sourceri.invalid/"] }]
+}"#;
+
+fn endpoint(provider: &str, endpoint: &str) -> (String, u64, String) {
+    (provider.to_owned(), 1, endpoint.to_owned())
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn minor_update() {
+    let fetch = SharedFetch::default();
+    fetch.set("file://one", list(1, 0, "2004-08-08T00:00:00Z", FRUSTRATA));
+
+    let mut subscription = Subscription::new(fetch.clone(), Source::Uri("file://one".into()));
+    let mut pool = LocalPool::new();
+
+    let update = pool.run_until(subscription.refresh()).unwrap();
+    assert!(update.diff.is_empty());
+    assert_eq!(subscription.current().unwrap().version().major, 1);
+
+    let unchanged = pool.run_until(subscription.refresh()).unwrap();
+    assert!(unchanged.diff.is_empty());
+    assert_eq!(unchanged.diff.bump(), Bump::None);
+
+    let providers = format!("{}, {}", FRUSTRATA_TWO, SOURCERI);
+    fetch.set("file://one", list(1, 1, "2005-08-08T00:00:00Z", &providers));
+
+    let update = pool.run_until(subscription.refresh()).unwrap();
+    assert_eq!(update.diff.bump(), Bump::Minor);
+    assert!(!update.requires_consent);
+
+    let mut added = Vec::new();
+    let mut removed = Vec::new();
+    let mut providers = Vec::new();
+
+    for entry in &update.diff.entries {
+        match entry {
+            Entry::EndpointAdded {
+                provider,
+                chain_id,
+                endpoint,
+                ..
+            } => added.push((provider.clone(), *chain_id, endpoint.clone())),
+            Entry::EndpointRemoved {
+                provider,
+                chain_id,
+                endpoint,
+                ..
+            } => removed.push((provider.clone(), *chain_id, endpoint.clone())),
+            Entry::ProviderAdded { provider, .. } => providers.push(provider.clone()),
+            Entry::Version { .. } | Entry::Timestamp { .. } => (),
+            other => panic!("unexpected entry: {:?}", other),
+        }
+    }
+
+    assert_eq!(
+        added,
+        [endpoint("frustrata", "https://two.frustrata.invalid/")]
+    );
+    assert_eq!(
+        removed,
+        [endpoint("frustrata", "https://one.frustrata.invalid/")]
+    );
+    assert_eq!(providers, ["sourceri"]);
+
+    assert_eq!(subscription.current().unwrap().version().minor, 1);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn major_update_needs_consent() {
+    let fetch = SharedFetch::default();
+    fetch.set("file://one", list(1, 0, "2004-08-08T00:00:00Z", FRUSTRATA));
+
+    let mut subscription = Subscription::new(fetch.clone(), Source::Uri("file://one".into()));
+    let mut pool = LocalPool::new();
+    pool.run_until(subscription.refresh()).unwrap();
+
+    fetch.set("file://one", list(2, 0, "2005-08-08T00:00:00Z", SOURCERI));
+
+    let update = pool.run_until(subscription.refresh()).unwrap();
+    assert_eq!(update.diff.bump(), Bump::Major);
+    assert!(update.requires_consent);
+
+    let removed =
+        update.diff.entries.iter().any(
+            |c| matches!(c, Entry::ProviderRemoved { provider, .. } if provider == "frustrata"),
+        );
+    assert!(removed);
+
+    assert_eq!(subscription.current().unwrap().version().major, 1);
+    assert_eq!(subscription.pending().unwrap().version().major, 2);
+
+    assert!(subscription.accept());
+    assert!(subscription.pending().is_none());
+    assert_eq!(subscription.current().unwrap().version().major, 2);
+    assert!(!subscription.accept());
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn downgrade_needs_consent() {
+    let fetch = SharedFetch::default();
+    fetch.set("file://one", list(2, 1, "2005-08-08T00:00:00Z", FRUSTRATA));
+
+    let mut subscription = Subscription::new(fetch.clone(), Source::Uri("file://one".into()));
+    let mut pool = LocalPool::new();
+    pool.run_until(subscription.refresh()).unwrap();
+
+    for (major, minor) in [(1, 0), (2, 0)] {
+        fetch.set(
+            "file://one",
+            list(major, minor, "2006-08-08T00:00:00Z", SOURCERI),
+        );
+
+        let update = pool.run_until(subscription.refresh()).unwrap();
+        assert_eq!(update.diff.bump(), Bump::Downgrade);
+        assert!(update.requires_consent);
+
+        let current = subscription.current().unwrap().version();
+        assert_eq!((current.major, current.minor), (2, 1));
+
+        let pending = subscription.pending().unwrap().version();
+        assert_eq!((pending.major, pending.minor), (major, minor));
+    }
+
+    assert!(subscription.reject().is_some());
+    assert_eq!(subscription.current().unwrap().version().major, 2);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn stale_update_rejected() {
+    let fetch = SharedFetch::default();
+    fetch.set("file://one", list(1, 0, "2004-08-08T00:00:00Z", FRUSTRATA));
+
+    let mut subscription = Subscription::new(fetch.clone(), Source::Uri("file://one".into()));
+    let mut pool = LocalPool::new();
+    pool.run_until(subscription.refresh()).unwrap();
+
+    fetch.set("file://one", list(1, 1, "2003-08-08T00:00:00Z", FRUSTRATA));
+
+    let err = pool.run_until(subscription.refresh()).unwrap_err();
+    assert_eq!(err.code(), "TIMESTAMP_REGRESSION");
+    assert_eq!(subscription.current().unwrap().version().minor, 0);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn poll_on_schedule() {
+    let fetch = SharedFetch::default();
+    fetch.set("file://one", list(1, 0, "2004-08-08T00:00:00Z", FRUSTRATA));
+
+    let mut subscription = Subscription::new(fetch, Source::Uri("file://one".into()))
+        .interval(Duration::from_secs(60));
+    let mut pool = LocalPool::new();
+
+    let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+
+    assert!(pool.run_until(subscription.poll(start)).is_some());
+    assert!(subscription.current().is_some());
+
+    let soon = start + Duration::from_secs(59);
+    assert!(!subscription.is_due(soon));
+    assert!(pool.run_until(subscription.poll(soon)).is_none());
+
+    let later = start + Duration::from_secs(60);
+    assert!(subscription.is_due(later));
+    assert!(pool.run_until(subscription.poll(later)).unwrap().is_ok());
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn bump_levels() {
+    let version = |major, minor, patch| {
+        let mut list = eip5139::RpcProviders::default();
+        let version = list.version_mut();
+        version.major = major;
+        version.minor = minor;
+        version.patch = patch;
+        list.version().clone()
+    };
+
+    let v1 = version(1, 0, 0);
+    assert_eq!(Bump::between(&v1, &version(1, 0, 1)), Bump::Patch);
+    assert_eq!(Bump::between(&v1, &version(1, 2, 0)), Bump::Minor);
+    assert_eq!(Bump::between(&v1, &version(2, 0, 0)), Bump::Major);
+    assert_eq!(Bump::between(&v1, &version(0, 9, 0)), Bump::Downgrade);
+    assert_eq!(Bump::between(&v1, &v1), Bump::None);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn source_policy_enforced() {
+    let fetch = SharedFetch::default();
+    fetch.set("file://one", list(1, 0, "2004-08-08T00:00:00Z", FRUSTRATA));
+
+    let mut subscription = Subscription::new(fetch, Source::Uri("file://one".into()))
+        .policy(Rules::new().allow_scheme("https"));
+    let mut pool = LocalPool::new();
+
+    for _ in 0..2 {
+        match pool.run_until(subscription.refresh()) {
+            Err(Error::Policy {
+                source: PolicyError::Scheme { .. },
+                ..
+            }) => (),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    assert!(subscription.current().is_none());
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn trust_policy_enforced() {
+    let fetch = SharedFetch::default();
+    fetch.set("file://one", list(1, 0, "2004-08-08T00:00:00Z", FRUSTRATA));
+
+    let signer = "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf"
+        .parse()
+        .unwrap();
+    let trust = TrustPolicy::new().trust(Source::Uri("file://one".into()), signer);
+
+    let mut subscription = Subscription::new(fetch, Source::Uri("file://one".into())).trust(trust);
+    let mut pool = LocalPool::new();
+
+    let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+    match pool.run_until(subscription.poll(start)) {
+        Some(Err(Error::Unsigned { .. })) => (),
+        other => panic!("{:?}", other),
+    }
+
+    assert!(subscription.current().is_none());
+}
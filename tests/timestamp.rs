@@ -0,0 +1,134 @@
+pub mod utils;
+
+use eip5139::{Error, RpcProviders, Source, Timestamp};
+
+use futures_executor::LocalPool;
+
+use self::utils::Fetch;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn root(version: &str, timestamp: &str) -> String {
+    format!(
+        r#"{{
+  "name": "Root List",
+  "version": {},
+  "timestamp": "{}",
+  "providers": {{}}
+}}"#,
+        version, timestamp
+    )
+}
+
+fn extension(timestamp: &str) -> String {
+    format!(
+        r#"{{
+  "name": "Extension List",
+  "version": {{
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  }},
+  "timestamp": "{}",
+  "extends": {{
+    "uri": "file://one",
+    "version": {{
+      "major": 1,
+      "minor": 0,
+      "patch": 0
+    }}
+  }},
+  "changes": []
+}}"#,
+        timestamp
+    )
+}
+
+const V1: &str = r#"{"major": 1, "minor": 0, "patch": 0}"#;
+const V1_1: &str = r#"{"major": 1, "minor": 1, "patch": 0}"#;
+
+fn resolve(fetch: Fetch, uri: &str) -> Result<RpcProviders, Error> {
+    let mut pool = LocalPool::new();
+    pool.run_until(RpcProviders::fetch(fetch, Source::Uri(uri.into())))
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn parse_and_compare() {
+    let utc: Timestamp = "2024-08-08T00:00:00Z".parse().unwrap();
+    let offset: Timestamp = "2024-08-08T02:00:00+02:00".parse().unwrap();
+    let later: Timestamp = "2024-08-08T00:00:00.5Z".parse().unwrap();
+
+    assert_eq!(utc, offset);
+    assert!(utc < later);
+    assert_eq!(offset.to_string(), "2024-08-08T02:00:00+02:00");
+    assert_eq!(utc.unix_timestamp(), 1_723_075_200);
+
+    assert!("yesterday".parse::<Timestamp>().is_err());
+    assert!("2024-08-08".parse::<Timestamp>().is_err());
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn extension_not_before_parent() {
+    let fetch = Fetch::with_two(
+        root(V1, "2004-08-08T00:00:00Z"),
+        extension("2004-08-08T00:00:00Z"),
+    );
+    let list = resolve(fetch, "file://two").unwrap();
+    assert_eq!(list.timestamp, "2004-08-08T00:00:00Z");
+
+    let fetch = Fetch::with_two(
+        root(V1, "2004-08-08T00:00:00Z"),
+        extension("2004-08-07T23:59:59Z"),
+    );
+    let err = resolve(fetch, "file://two").unwrap_err();
+
+    assert_eq!(err.code(), "TIMESTAMP_ORDER");
+
+    match err {
+        Error::TimestampOrder {
+            child,
+            timestamp,
+            parent,
+            parent_timestamp,
+            ..
+        } => {
+            assert_eq!(child, Source::Uri("file://two".into()));
+            assert_eq!(*timestamp, "2004-08-07T23:59:59Z");
+            assert_eq!(parent, Source::Uri("file://one".into()));
+            assert_eq!(*parent_timestamp, "2004-08-08T00:00:00Z");
+        }
+        other => panic!("expected TimestampOrder, but got: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn update_not_older() {
+    let old = resolve(
+        Fetch::with_one(root(V1, "2004-08-08T00:00:00Z")),
+        "file://one",
+    )
+    .unwrap();
+
+    let newer = resolve(
+        Fetch::with_one(root(V1_1, "2005-08-08T00:00:00Z")),
+        "file://one",
+    )
+    .unwrap();
+    old.check_update(&newer).unwrap();
+
+    // Going back to an older version isn't a regression.
+    newer.check_update(&old).unwrap();
+
+    let stale = resolve(
+        Fetch::with_one(root(V1_1, "2003-08-08T00:00:00Z")),
+        "file://one",
+    )
+    .unwrap();
+    let err = old.check_update(&stale).unwrap_err();
+
+    assert_eq!(err.code(), "TIMESTAMP_REGRESSION");
+}
@@ -1,12 +1,34 @@
-use eip5139::errors::FetchError;
-use eip5139::Source;
+#![allow(dead_code)]
 
+use eip5139::errors::{FetchError, TransportError};
+use eip5139::transport::Transport;
+use eip5139::{RpcProviders, Source};
+
+use futures_executor::LocalPool;
+
+use serde_json::{json, Value};
+
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::future::Future;
+use std::io;
 use std::pin::Pin;
+use std::rc::Rc;
+
+/// Resolve `list` as the root list `file://one`.
+pub fn fetch_list<L>(list: L) -> RpcProviders
+where
+    L: Into<String>,
+{
+    let fetch = Fetch::with_one(list);
+    let mut pool = LocalPool::new();
+    pool.run_until(RpcProviders::fetch(fetch, Source::Uri("file://one".into())))
+        .unwrap()
+}
 
 pub struct Fetch {
     contents: HashMap<Source, String>,
+    signatures: HashMap<Source, String>,
 }
 
 impl Fetch {
@@ -16,7 +38,10 @@ impl Fetch {
     {
         let mut contents = HashMap::new();
         contents.insert(Source::Uri("file://one".into()), one.into());
-        Self { contents }
+        Self {
+            contents,
+            signatures: HashMap::new(),
+        }
     }
 
     pub fn with_two<O, T>(one: O, two: T) -> Self
@@ -27,7 +52,36 @@ impl Fetch {
         let mut contents = HashMap::new();
         contents.insert(Source::Uri("file://one".into()), one.into());
         contents.insert(Source::Uri("file://two".into()), two.into());
-        Self { contents }
+        Self {
+            contents,
+            signatures: HashMap::new(),
+        }
+    }
+
+    pub fn with_signature<U, S>(mut self, uri: U, signature: S) -> Self
+    where
+        U: Into<String>,
+        S: Into<String>,
+    {
+        self.signatures
+            .insert(Source::Uri(uri.into()), signature.into());
+        self
+    }
+
+    pub fn with_three<O, T, H>(one: O, two: T, three: H) -> Self
+    where
+        O: Into<String>,
+        T: Into<String>,
+        H: Into<String>,
+    {
+        let mut contents = HashMap::new();
+        contents.insert(Source::Uri("file://one".into()), one.into());
+        contents.insert(Source::Uri("file://two".into()), two.into());
+        contents.insert(Source::Uri("file://three".into()), three.into());
+        Self {
+            contents,
+            signatures: HashMap::new(),
+        }
     }
 }
 
@@ -39,4 +93,133 @@ impl eip5139::Fetch for Fetch {
         let output = Ok(self.contents[&source].to_owned());
         Box::pin(async move { output })
     }
+
+    fn fetch_signature(
+        &mut self,
+        source: Source,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<String>, FetchError>>>> {
+        let output = Ok(self.signatures.get(&source).cloned());
+        Box::pin(async move { output })
+    }
+}
+
+/// A fetch whose contents can be changed while it's in use.
+#[derive(Debug, Clone, Default)]
+pub struct SharedFetch {
+    contents: Rc<RefCell<HashMap<Source, String>>>,
+}
+
+impl SharedFetch {
+    pub fn set<U, C>(&self, uri: U, contents: C)
+    where
+        U: Into<String>,
+        C: Into<String>,
+    {
+        self.contents
+            .borrow_mut()
+            .insert(Source::Uri(uri.into()), contents.into());
+    }
+}
+
+impl eip5139::Fetch for SharedFetch {
+    fn fetch(
+        &mut self,
+        source: Source,
+    ) -> Pin<Box<dyn Future<Output = Result<String, FetchError>>>> {
+        let output = Ok(self.contents.borrow()[&source].to_owned());
+        Box::pin(async move { output })
+    }
+}
+
+/// State of a node behind a mock JSON-RPC endpoint.
+#[derive(Debug, Clone)]
+pub enum Node {
+    /// Answers requests as a node on `chain_id` at block `block`.
+    Up { chain_id: u64, block: u64 },
+
+    /// Answers every request with a JSON-RPC error.
+    Error { code: i64 },
+
+    /// Fails to deliver requests.
+    Down,
+}
+
+/// An in-memory JSON-RPC server for a set of endpoints.
+#[derive(Clone, Default)]
+pub struct MockRpc {
+    nodes: Rc<RefCell<HashMap<String, Node>>>,
+    requests: Rc<RefCell<Vec<(String, String)>>>,
+}
+
+impl MockRpc {
+    pub fn with_nodes<I, E>(nodes: I) -> Self
+    where
+        I: IntoIterator<Item = (E, Node)>,
+        E: Into<String>,
+    {
+        let nodes = nodes.into_iter().map(|(e, n)| (e.into(), n)).collect();
+        Self {
+            nodes: Rc::new(RefCell::new(nodes)),
+            requests: Default::default(),
+        }
+    }
+
+    pub fn set<E>(&self, endpoint: E, node: Node)
+    where
+        E: Into<String>,
+    {
+        self.nodes.borrow_mut().insert(endpoint.into(), node);
+    }
+
+    /// Every `(endpoint, method)` requested so far.
+    pub fn requests(&self) -> Vec<(String, String)> {
+        self.requests.borrow().clone()
+    }
+}
+
+impl Transport for MockRpc {
+    fn send(
+        &mut self,
+        endpoint: &str,
+        request: Value,
+    ) -> Pin<Box<dyn Future<Output = Result<Value, TransportError>>>> {
+        let method = request["method"].as_str().unwrap().to_owned();
+        self.requests
+            .borrow_mut()
+            .push((endpoint.to_owned(), method.clone()));
+
+        let node = self
+            .nodes
+            .borrow()
+            .get(endpoint)
+            .cloned()
+            .unwrap_or(Node::Down);
+
+        let output = match node {
+            Node::Down => Err(TransportError::custom(io::Error::from(
+                io::ErrorKind::ConnectionRefused,
+            ))),
+            Node::Error { code } => Ok(json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "error": { "code": code, "message": "mock error" },
+            })),
+            Node::Up { chain_id, block } => {
+                let result = match method.as_str() {
+                    "eth_chainId" => json!(format!("{:#x}", chain_id)),
+                    "net_version" => json!(chain_id.to_string()),
+                    "eth_blockNumber" => json!(format!("{:#x}", block)),
+                    _ => json!(method),
+                };
+
+                Ok(json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "result": result,
+                }))
+            }
+        };
+
+        Box::pin(async move { output })
+    }
 }
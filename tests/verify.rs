@@ -0,0 +1,119 @@
+pub mod utils;
+
+use eip5139::verify::{Disagreement, Verifier};
+use eip5139::{RpcProviders, Source};
+
+use futures_executor::LocalPool;
+
+use self::utils::{Fetch, MockRpc, Node};
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const LIST: &str = r#"{
+  "name": "Root List",
+  "version": {
+    "major": 1,
+    "minor": 0,
+    "patch": 0
+  },
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "providers": {
+    "honest": {
+      "name": "Honest",
+      "chains": [
+        {
+          "chainId": 1,
+          "endpoints": ["https://mainnet.honest.invalid/"]
+        }
+      ]
+    },
+    "liar": {
+      "name": "Liar",
+      "chains": [
+        {
+          "chainId": 1,
+          "endpoints": [
+            "https://mainnet.liar.invalid/",
+            "https://really-goerli.liar.invalid/"
+          ]
+        },
+        {
+          "chainId": 10,
+          "endpoints": ["https://really-mainnet.liar.invalid/"]
+        }
+      ]
+    }
+  }
+}"#;
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn detect_and_prune() {
+    let mut pool = LocalPool::new();
+
+    let fetch = Fetch::with_one(LIST);
+    let mut list = pool
+        .run_until(RpcProviders::fetch(fetch, Source::Uri("file://one".into())))
+        .unwrap();
+
+    let mainnet = Node::Up {
+        chain_id: 1,
+        block: 1,
+    };
+
+    let rpc = MockRpc::with_nodes([
+        ("https://mainnet.honest.invalid/", mainnet.clone()),
+        ("https://mainnet.liar.invalid/", mainnet.clone()),
+        (
+            "https://really-goerli.liar.invalid/",
+            Node::Up {
+                chain_id: 5,
+                block: 1,
+            },
+        ),
+        ("https://really-mainnet.liar.invalid/", mainnet),
+    ]);
+
+    let report = pool.run_until(Verifier::new(rpc).net_version(true).verify(&list));
+
+    assert!(!report.is_consistent());
+    assert!(report.unverified.is_empty());
+    assert_eq!(report.mismatches.len(), 2);
+
+    assert_eq!(report.mismatches[0].provider, "liar");
+    assert_eq!(report.mismatches[0].chain_id, 1);
+    match report.mismatches[0].disagreement {
+        Disagreement::ChainId { actual, .. } => assert_eq!(actual, 5),
+        ref other => panic!("expected ChainId, got {:?}", other),
+    }
+    assert_eq!(report.mismatches[1].chain_id, 10);
+
+    assert_eq!(report.prune(&mut list), 2);
+    assert_eq!(list.chain_ids().collect::<Vec<_>>(), [1]);
+
+    let endpoints: Vec<_> = list.endpoints_for(1).map(|e| e.endpoint).collect();
+    assert_eq!(
+        endpoints,
+        [
+            "https://mainnet.honest.invalid/",
+            "https://mainnet.liar.invalid/"
+        ]
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn unreachable_is_unverified() {
+    let mut pool = LocalPool::new();
+
+    let fetch = Fetch::with_one(LIST);
+    let list = pool
+        .run_until(RpcProviders::fetch(fetch, Source::Uri("file://one".into())))
+        .unwrap();
+
+    let report = pool.run_until(Verifier::new(MockRpc::default()).verify(&list));
+
+    assert!(report.is_consistent());
+    assert_eq!(report.unverified.len(), 4);
+}
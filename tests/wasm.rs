@@ -25,6 +25,9 @@ async fn throws() {
         .await
         .unwrap_err();
 
+    let code = js_sys::Reflect::get(&err, &JsValue::from_str("code")).unwrap();
+    assert_eq!(code, "FETCH");
+
     let err = js_sys::Error::from(err);
     let cause = js_sys::Error::from(err.cause());
     assert_eq!(cause.message(), "hello world");
@@ -152,3 +155,70 @@ async fn providers() {
         ])
     );
 }
+
+#[wasm_bindgen_test]
+async fn add_ethereum_chain_parameter() {
+    let one = r#"{
+  "name": "Root List",
+  "version": {
+    "major": 0,
+    "minor": 1,
+    "patch": 1
+  },
+  "timestamp": "2004-08-08T00:00:00.0Z",
+  "providers": {
+    "foo": {
+        "name": "Sourceri",
+        "logo": "https://sourceri.invalid/logo.png",
+        "chains": [
+            {
+                "chainId": 1,
+                "endpoints": [
+                    "https://mainnet.sourceri.invalid/"
+                ]
+            }
+        ]
+    }
+  }
+}"#;
+
+    let fetch = Fetch::with_one(one);
+    let list = RpcProviders::fetch(fetch, Source::Uri("file://one".into()))
+        .await
+        .unwrap();
+
+    let parameter: serde_json::Value = list
+        .add_ethereum_chain_parameter_js(1.0, None)
+        .ok()
+        .flatten()
+        .unwrap()
+        .into_serde()
+        .unwrap();
+
+    assert_eq!(
+        parameter,
+        json!({
+            "chainId": "0x1",
+            "chainName": "Ethereum Mainnet",
+            "nativeCurrency": {
+                "name": "Ether",
+                "symbol": "ETH",
+                "decimals": 18,
+            },
+            "rpcUrls": ["https://mainnet.sourceri.invalid/"],
+            "blockExplorerUrls": [
+                "https://etherscan.io",
+                "https://eth.blockscout.com",
+                "https://ethereum.dex.guru",
+                "https://ethereum.routescan.io",
+            ],
+            "iconUrls": ["https://sourceri.invalid/logo.png"],
+        })
+    );
+
+    assert!(matches!(
+        list.add_ethereum_chain_parameter_js(5.0, None),
+        Ok(None)
+    ));
+    assert!(list.add_ethereum_chain_parameter_js(1.5, None).is_err());
+}